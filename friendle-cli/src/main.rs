@@ -0,0 +1,172 @@
+//! Play Friendle in the terminal with the same rules and word list as the Discord bot.
+
+mod terminal;
+
+use anyhow::{bail, Context};
+use friendle_core::i18n::{Locale, Localize};
+use friendle_core::keyboard::KeyboardLayout;
+use friendle_core::model::coding::{decode, encode, Code};
+use friendle_core::model::difficulty::DifficultyEstimator;
+use friendle_core::model::evaluation::EmojiTheme;
+use friendle_core::model::game::{Game, GameState, StrictMode};
+use friendle_core::model::guess_error::{GuessError, InvalidWordError};
+use friendle_core::model::validate_word::validate_word_format;
+use friendle_core::word_list::{parse_word_list, DEFAULT_WORD_LIST_PATH, WORD_LIST_PATH_ENV_VAR};
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+  friendle-cli encode <word>    encode a word as a code
+  friendle-cli play <code>      play the word with the given code";
+
+const PLAY_HELP: &str = "Type a word to guess it. Other commands:
+  :mode [off|soft|nyt|strict]  show or change the strict mode
+  :share                       print the result to share
+  :help                        show this help
+  :quit                        give up and quit";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["encode", word] => encode_word(word),
+        ["play", code] => play(code),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads the word list from the same place the bot does.
+fn load_word_list() -> anyhow::Result<HashSet<String>> {
+    let path = std::env::var(WORD_LIST_PATH_ENV_VAR)
+        .unwrap_or_else(|_| String::from(DEFAULT_WORD_LIST_PATH));
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to load word list from {path}"))?;
+    let (words, errors) = parse_word_list(&text);
+    for err in errors {
+        eprintln!("{err}");
+    }
+    Ok(words)
+}
+
+fn encode_word(word: &str) -> anyhow::Result<()> {
+    let word = word.to_ascii_lowercase();
+    if validate_word_format(&word).is_err() {
+        bail!(
+            "Invalid word format: {word}. Only five-letter words with letters a-z are supported."
+        );
+    }
+    let word_list = load_word_list()?;
+    let difficulty = DifficultyEstimator::new(&word_list).estimate(&word, &word_list);
+    println!("Code: {}", encode(&word).value);
+    println!("Estimated difficulty: {difficulty}.");
+    if !word_list.contains(&word) {
+        println!("Note that your word is not in the original Wordle word list.");
+    }
+    Ok(())
+}
+
+fn play(code: &str) -> anyhow::Result<()> {
+    let word_list = load_word_list()?;
+    let code = Code {
+        value: code
+            .parse()
+            .with_context(|| format!("Invalid code: {code}"))?,
+    };
+    let solution = decode(code).with_context(|| format!("Invalid code: {}", code.value))?;
+    let mut game = Game::new(code, solution, &word_list)
+        .with_context(|| format!("Invalid code: {}", code.value))?;
+    let theme = EmojiTheme::default();
+
+    println!("{PLAY_HELP}");
+    print_game(&game, &theme);
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        let input = line.trim().to_ascii_lowercase();
+        match input.split_whitespace().collect::<Vec<_>>()[..] {
+            [] => continue,
+            [":quit"] => break,
+            [":help"] => println!("{PLAY_HELP}"),
+            [":share"] => print_share_text(&game),
+            [":mode"] => println!(
+                "Current mode: {}. Available: {}",
+                game.get_strict_mode().name(),
+                game.mode_switch_options()
+                    .iter()
+                    .map(|mode| mode.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            [":mode", name] => match StrictMode::ALL.into_iter().find(|mode| mode.name() == name) {
+                Some(mode) => match game.set_strict_mode(mode) {
+                    Ok(()) => println!("Switched to mode {}.", mode.name()),
+                    Err(err) => println!("{}", err.localize(Locale::En)),
+                },
+                None => println!("Unknown mode: {name}"),
+            },
+            [guess] if !guess.starts_with(':') => {
+                if let Err(err) = game.guess(String::from(guess), &word_list) {
+                    print_guess_error(&game, guess, &err, &word_list);
+                    continue;
+                }
+                print_game(&game, &theme);
+                match game.state() {
+                    GameState::InProgress => {}
+                    GameState::Won => {
+                        println!("You won! Good job :)");
+                        print_share_text(&game);
+                        break;
+                    }
+                    GameState::Lost => {
+                        println!(
+                            "Unfortunately, you're out of tries. The solution was {}",
+                            game.solution()
+                        );
+                        print_share_text(&game);
+                        break;
+                    }
+                }
+            }
+            _ => println!("Unknown command. Type :help for a list of commands."),
+        }
+    }
+    Ok(())
+}
+
+fn print_game(game: &Game, theme: &EmojiTheme) {
+    let mut output = String::new();
+    terminal::display_board(game, theme, &mut output);
+    output.push('\n');
+    terminal::display_keyboard(game.knowledge(), theme, &mut output);
+    println!("{output}");
+}
+
+fn print_share_text(game: &Game) {
+    if game.state() == GameState::InProgress {
+        println!("The current game isn't finished yet.");
+    } else {
+        print!("{}", terminal::share_text(game));
+    }
+}
+
+fn print_guess_error(game: &Game, guess: &str, err: &GuessError, word_list: &HashSet<String>) {
+    println!("{}", err.localize(Locale::En));
+    if let GuessError::InvalidWord(
+        InvalidWordError::NotInWordList { .. } | InvalidWordError::WrongLength { .. },
+    ) = err
+    {
+        let suggestions = game.suggestions(guess, word_list, KeyboardLayout::default());
+        if !suggestions.is_empty() {
+            println!("Did you mean {}?", suggestions.join(", "));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use friendle_core::constants::{MAX_GUESSES, WORD_LENGTH};
+use friendle_core::i18n::Locale;
+use friendle_core::keyboard::KEYBOARD_LAYOUT;
+use friendle_core::model::evaluation::{get_warning_emoji, EmojiMode, EmojiTheme};
+use friendle_core::model::game::Game;
+use friendle_core::model::knowledge::Knowledge;
+use friendle_core::render::Renderer;
+
+const ANSI_RESET: &str = "\u{1b}[0m";
+
+/// Renders the guesses as colored tiles, with the remaining guesses as empty tiles.
+pub fn display_board(game: &Game, theme: &EmojiTheme, renderer: &mut impl Renderer) {
+    for guess in game.history() {
+        for (letter, evaluation) in guess.word.chars().zip(&guess.evaluation) {
+            // ANSI background colors are the foreground colors offset by 10.
+            let background = theme.ansi_color(*evaluation) + 10;
+            renderer.push(&format!(
+                "\u{1b}[1;97;{background}m {} {ANSI_RESET}",
+                letter.to_ascii_uppercase()
+            ));
+        }
+        if guess.warning.is_some() {
+            // mark wasted guesses
+            renderer.push(&format!(" {}", get_warning_emoji(EmojiMode::Unicode)));
+        }
+        renderer.push_line("");
+    }
+    for _ in game.history().len()..MAX_GUESSES {
+        renderer.push_line(&" _ ".repeat(WORD_LENGTH));
+    }
+}
+
+/// Renders the keyboard with every letter colored by what is known about it.
+pub fn display_keyboard(knowledge: &Knowledge, theme: &EmojiTheme, renderer: &mut impl Renderer) {
+    for (row_index, row) in KEYBOARD_LAYOUT.iter().enumerate() {
+        renderer.push(&" ".repeat(row_index));
+        for c in row.chars() {
+            let letter = c.to_ascii_uppercase();
+            match knowledge.letter_state(c).to_evaluation() {
+                Some(evaluation) => renderer.push(&format!(
+                    "\u{1b}[1;{}m{letter}{ANSI_RESET} ",
+                    theme.ansi_color(evaluation)
+                )),
+                None => renderer.push(&format!("{letter} ")),
+            }
+        }
+        renderer.push_line("");
+    }
+}
+
+/// The result of a finished game, exactly as the Copy Result button shares it with default settings.
+pub fn share_text(game: &Game) -> String {
+    let mut text = String::new();
+    game.display_game_state_header(&mut text, Locale::En);
+    game.display_state(&mut text, EmojiMode::DiscordName, &EmojiTheme::default());
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use friendle_core::model::coding::encode;
+    use std::collections::HashSet;
+
+    fn game_with_guesses(guesses: &[&str]) -> Game {
+        let word_list = HashSet::from_iter(["tales", "lanes"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        for guess in guesses {
+            game.guess(String::from(*guess), &word_list).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_display_board() {
+        let game = game_with_guesses(&["lanes"]);
+        let mut board = String::new();
+        display_board(&game, &EmojiTheme::Classic, &mut board);
+        let lines = board.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), MAX_GUESSES);
+        // "lanes" vs "tales": L present, A correct, N absent.
+        assert!(lines[0].starts_with(
+            "\u{1b}[1;97;43m L \u{1b}[0m\u{1b}[1;97;42m A \u{1b}[0m\u{1b}[1;97;41m N \u{1b}[0m"
+        ));
+        assert_eq!(lines[1], " _  _  _  _  _ ");
+    }
+
+    #[test]
+    fn test_display_keyboard() {
+        let game = game_with_guesses(&["lanes"]);
+        let mut keyboard = String::new();
+        display_keyboard(game.knowledge(), &EmojiTheme::Nyt, &mut keyboard);
+        let lines = keyboard.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), KEYBOARD_LAYOUT.len());
+        assert!(lines[0].starts_with("Q W \u{1b}[1;32mE\u{1b}[0m R T "));
+        assert!(lines[2].contains("\u{1b}[1;30mN\u{1b}[0m "));
+    }
+
+    #[test]
+    fn test_share_text() {
+        let game = game_with_guesses(&["lanes", "tales"]);
+        let code = game.code().value;
+        assert_eq!(
+            share_text(&game),
+            format!(
+                "Friendle `{code}`: 2/6 \n\
+                 :yellow_square::green_square::red_square::green_square::green_square:\n\
+                 :green_square::green_square::green_square::green_square::green_square:\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::model::difficulty::Difficulty;
+use crate::model::evaluation::Evaluation;
+use crate::model::game::ModeChangeError;
+use crate::model::guess_error::{GuessError, InvalidWordError};
+use crate::model::knowledge::{CharacterBound, KnowledgeValidationError};
+
+/// Languages the bot can talk to users in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// Picks the translation for this locale.
+    pub fn pick<T>(self, en: T, de: T) -> T {
+        match self {
+            Locale::En => en,
+            Locale::De => de,
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// Renders a value as user-facing text in the given language.
+/// `Display` implementations stay English and are meant for logs.
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for Difficulty {
+    fn localize(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.to_string(),
+            Locale::De => String::from(match self {
+                Difficulty::Easy => "leicht",
+                Difficulty::Medium => "mittel",
+                Difficulty::Hard => "schwer",
+                Difficulty::Evil => "teuflisch",
+            }),
+        }
+    }
+}
+
+impl Localize for CharacterBound {
+    fn localize(&self, locale: Locale) -> String {
+        let count = match (self.count(), locale) {
+            (1, Locale::En) => String::from("once"),
+            (2, Locale::En) => String::from("twice"),
+            (n, Locale::En) => format!("{n} times"),
+            (1, Locale::De) => String::from("einmal"),
+            (2, Locale::De) => String::from("zweimal"),
+            (n, Locale::De) => format!("{n}-mal"),
+        };
+        match self {
+            CharacterBound::Minimum(_) => {
+                locale.pick(format!("at least {count}"), format!("mindestens {count}"))
+            }
+            CharacterBound::Exact(_) => {
+                locale.pick(format!("exactly {count}"), format!("genau {count}"))
+            }
+        }
+    }
+}
+
+impl Localize for Evaluation {
+    fn localize(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => String::from(self.description()),
+            Locale::De => String::from(match self {
+                Evaluation::Absent => "fehlt",
+                Evaluation::Present => "vorhanden",
+                Evaluation::Correct => "richtig",
+            }),
+        }
+    }
+}
+
+/// Catalog of the texts the game renders itself, e.g. in result headers.
+#[derive(Debug, Clone, Copy)]
+pub enum GameMessage {
+    InProgress,
+    NotInWordList,
+    WastedGuesses(usize),
+    GuessNumber(usize),
+    CountsAsWasted,
+    /// Short form of a letter count, e.g. "at least 2".
+    LetterCount(CharacterBound),
+}
+
+impl Localize for GameMessage {
+    fn localize(&self, locale: Locale) -> String {
+        match *self {
+            GameMessage::InProgress => String::from(locale.pick("[in progress]", "[läuft]")),
+            GameMessage::NotInWordList => {
+                String::from(locale.pick("[not in word list]", "[nicht in der Wortliste]"))
+            }
+            GameMessage::WastedGuesses(count) => locale.pick(
+                format!("({count} wasted)"),
+                format!("({count} verschwendet)"),
+            ),
+            GameMessage::GuessNumber(number) => {
+                locale.pick(format!("Guess {number}:"), format!("Versuch {number}:"))
+            }
+            GameMessage::CountsAsWasted => {
+                String::from(locale.pick("Counts as wasted.", "Zählt als verschwendet."))
+            }
+            GameMessage::LetterCount(bound) => match bound {
+                CharacterBound::Minimum(count) => {
+                    locale.pick(format!("at least {count}"), format!("mindestens {count}"))
+                }
+                CharacterBound::Exact(count) => {
+                    locale.pick(format!("exactly {count}"), format!("genau {count}"))
+                }
+            },
+        }
+    }
+}
+
+impl Localize for KnowledgeValidationError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            KnowledgeValidationError::UnusedFixedCharacter {
+                position,
+                given_character,
+                correct_character,
+            } => locale.pick(
+                format!(
+                    "Letter in position {} is known to be `{correct_character}` but `{given_character}` was given.",
+                    position + 1
+                ),
+                format!(
+                    "Der Buchstabe an Position {} ist bekanntermaßen `{correct_character}`, aber `{given_character}` wurde angegeben.",
+                    position + 1
+                ),
+            ),
+            KnowledgeValidationError::IncorrectPlacement {
+                position,
+                character,
+            } => locale.pick(
+                format!(
+                    "Letter in position {} is known to not be `{character}`.",
+                    position + 1
+                ),
+                format!(
+                    "Der Buchstabe an Position {} ist bekanntermaßen nicht `{character}`.",
+                    position + 1
+                ),
+            ),
+            KnowledgeValidationError::WrongCount {
+                character, bound, ..
+            } => {
+                let bound = bound.localize(locale);
+                locale.pick(
+                    format!("Letter `{character}` is known to occur {bound}."),
+                    format!("Der Buchstabe `{character}` kommt bekanntermaßen {bound} vor."),
+                )
+            }
+            KnowledgeValidationError::AbsentLetter { character } => locale.pick(
+                format!("Letter `{character}` is known to not be in the word."),
+                format!("Der Buchstabe `{character}` kommt bekanntermaßen nicht im Wort vor."),
+            ),
+        }
+    }
+}
+
+impl Localize for InvalidWordError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            InvalidWordError::WrongLength {
+                expected_length,
+                given_length,
+            } => locale.pick(
+                format!("Expected word of length {expected_length}, received word of length {given_length}"),
+                format!("Erwartet wurde ein Wort der Länge {expected_length}, erhalten wurde eines der Länge {given_length}"),
+            ),
+            InvalidWordError::NonLatinAlpha => String::from(locale.pick(
+                "Only English words with letters A-Z are supported",
+                "Nur englische Wörter mit den Buchstaben A-Z werden unterstützt",
+            )),
+            InvalidWordError::NotInWordList { word } => locale.pick(
+                format!("Given word {word} is not in the list of supported words"),
+                format!("Das Wort {word} ist nicht in der Liste der unterstützten Wörter"),
+            ),
+        }
+    }
+}
+
+impl Localize for GuessError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            GuessError::GameNotInProgress => String::from(locale.pick(
+                "No game is currently in progress.",
+                "Es läuft gerade kein Spiel.",
+            )),
+            GuessError::InvalidWord(err) => err.localize(locale),
+            GuessError::RejectedByStrictMode(err) => err.localize(locale),
+        }
+    }
+}
+
+impl Localize for ModeChangeError {
+    fn localize(&self, locale: Locale) -> String {
+        String::from(match self {
+            ModeChangeError::AlreadySet => locale.pick(
+                "Requested mode is already set.",
+                "Der gewünschte Modus ist bereits aktiv.",
+            ),
+            ModeChangeError::TooManyGuessesAlready => locale.pick(
+                "Cannot switch to a stricter mode with more than one guess.",
+                "Nach mehr als einem Versuch kann nicht mehr in einen strengeren Modus gewechselt werden.",
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_errors_match_display() {
+        let errors = [
+            GuessError::GameNotInProgress,
+            GuessError::InvalidWord(InvalidWordError::WrongLength {
+                expected_length: 5,
+                given_length: 3,
+            }),
+            GuessError::InvalidWord(InvalidWordError::NonLatinAlpha),
+            GuessError::InvalidWord(InvalidWordError::NotInWordList {
+                word: String::from("abcde"),
+            }),
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::UnusedFixedCharacter {
+                position: 0,
+                given_character: 'a',
+                correct_character: 'b',
+            }),
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::IncorrectPlacement {
+                position: 1,
+                character: 'c',
+            }),
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::WrongCount {
+                character: 'd',
+                given_count: 1,
+                bound: CharacterBound::Exact(2),
+            }),
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::AbsentLetter {
+                character: 'e',
+            }),
+        ];
+        for error in errors {
+            assert_eq!(error.localize(Locale::En), error.to_string());
+            assert_ne!(error.localize(Locale::De), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_german_errors_carry_data() {
+        let error = KnowledgeValidationError::WrongCount {
+            character: 'd',
+            given_count: 1,
+            bound: CharacterBound::Minimum(2),
+        };
+        assert_eq!(
+            error.localize(Locale::De),
+            "Der Buchstabe `d` kommt bekanntermaßen mindestens zweimal vor."
+        );
+    }
+
+    #[test]
+    fn test_game_messages() {
+        assert_eq!(
+            GameMessage::LetterCount(CharacterBound::Minimum(2)).localize(Locale::En),
+            "at least 2"
+        );
+        assert_eq!(
+            GameMessage::WastedGuesses(1).localize(Locale::De),
+            "(1 verschwendet)"
+        );
+        assert_eq!(Evaluation::Present.localize(Locale::En), "present");
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        for locale in Locale::ALL {
+            assert_eq!(locale.name().parse(), Ok(locale));
+        }
+        assert_eq!("DE".parse(), Ok(Locale::De));
+        assert_eq!("fr".parse::<Locale>(), Err(()));
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+pub const KEYBOARD_LAYOUT: &[&str] = &["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const AZERTY_LAYOUT: &[&str] = &["azertyuiop", "qsdfghjklm", "wxcvbn"];
+const QWERTZ_LAYOUT: &[&str] = &["qwertzuiop", "asdfghjkl", "yxcvbnm"];
+const DVORAK_LAYOUT: &[&str] = &["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"];
+const ALPHABETICAL_LAYOUT: &[&str] = &["abcdefghi", "jklmnopqr", "stuvwxyz"];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Alphabetical,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [KeyboardLayout; 5] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Alphabetical,
+    ];
+
+    /// The letter keys of the layout, row by row.
+    pub fn rows(self) -> &'static [&'static str] {
+        match self {
+            KeyboardLayout::Qwerty => KEYBOARD_LAYOUT,
+            KeyboardLayout::Azerty => AZERTY_LAYOUT,
+            KeyboardLayout::Qwertz => QWERTZ_LAYOUT,
+            KeyboardLayout::Dvorak => DVORAK_LAYOUT,
+            KeyboardLayout::Alphabetical => ALPHABETICAL_LAYOUT,
+        }
+    }
+
+    /// Where the first letter key of each row sits on a physical keyboard, in quarter keys
+    /// from the left of the top row's `Q` key position. `None` for layouts without physical keyboards.
+    pub fn row_offsets(self) -> Option<[usize; 3]> {
+        match self {
+            // The home row is shifted by a quarter key and the bottom row by three quarters.
+            KeyboardLayout::Qwerty | KeyboardLayout::Azerty | KeyboardLayout::Qwertz => {
+                Some([0, 1, 3])
+            }
+            // Punctuation takes the first three keys of the top row and the first key of the bottom row.
+            KeyboardLayout::Dvorak => Some([12, 1, 7]),
+            KeyboardLayout::Alphabetical => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyboardLayout::Qwerty => "qwerty",
+            KeyboardLayout::Azerty => "azerty",
+            KeyboardLayout::Qwertz => "qwertz",
+            KeyboardLayout::Dvorak => "dvorak",
+            KeyboardLayout::Alphabetical => "alphabetical",
+        }
+    }
+}
+
+impl FromStr for KeyboardLayout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KeyboardLayout::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+const REGIONAL_INDICATORS: &[char] = &[
+    '🇦', '🇧', '🇨', '🇩', '🇪', '🇫', '🇬', '🇭', '🇮', '🇯', '🇰', '🇱', '🇲', '🇳', '🇴', '🇵', '🇶', '🇷', '🇸',
+    '🇹', '🇺', '🇻', '🇼', '🇽', '🇾', '🇿',
+];
+
+fn get_regional_indicator(letter: char) -> char {
+    REGIONAL_INDICATORS[(letter.to_ascii_lowercase() as u32 - 'a' as u32) as usize]
+}
+
+pub fn get_regional_indicator_emoji_with_zero_width_space(c: char) -> String {
+    // add a zero-width space unicode character after each emoji to prevent Serenity from merging successive emojis.
+    format!("{}\u{200c}", get_regional_indicator(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regional_indicators() {
+        assert_eq!(get_regional_indicator('a'), '🇦');
+        assert_eq!(get_regional_indicator('z'), '🇿');
+    }
+
+    #[test]
+    fn test_all_letters_present_in_layout() {
+        for layout in KeyboardLayout::ALL {
+            let len: usize = layout.rows().iter().map(|s| s.len()).sum();
+            assert_eq!(len, 26, "{layout:?}");
+            let letters = layout
+                .rows()
+                .iter()
+                .flat_map(|row| row.chars())
+                .collect::<std::collections::HashSet<_>>();
+            assert!(('a'..='z').all(|c| letters.contains(&c)), "{layout:?}");
+        }
+    }
+
+    #[test]
+    fn test_layout_from_str() {
+        for layout in KeyboardLayout::ALL {
+            assert_eq!(layout.name().parse(), Ok(layout));
+        }
+        assert_eq!("QWERTZ".parse(), Ok(KeyboardLayout::Qwertz));
+        assert_eq!("colemak".parse::<KeyboardLayout>(), Err(()));
+    }
+}
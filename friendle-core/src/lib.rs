@@ -0,0 +1,9 @@
+//! The Friendle game engine: word encoding, evaluation, knowledge tracking and rule sets,
+//! independent of any frontend.
+
+pub mod constants;
+pub mod i18n;
+pub mod keyboard;
+pub mod model;
+pub mod render;
+pub mod word_list;
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+pub const MULTIPLIER: u32 = 30;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct Code {
+    pub value: u32,
+}
+
+pub fn encode(word: &str) -> Code {
+    // The first letter is the lowest digit. Starting from the highest one avoids computing
+    // a power of `MULTIPLIER` past the last letter, which would overflow for the longest codes.
+    let mut value = 0;
+    for c in word.to_lowercase().chars().rev() {
+        value = value * MULTIPLIER + encode_letter(c);
+    }
+
+    value = value.rotate_right(value.count_ones()); // Violet's algorithm
+
+    Code { value }
+}
+
+/// Decodes a code back into its word, or `None` if the code doesn't belong to a word.
+pub fn decode(code: Code) -> Option<String> {
+    let mut s = String::new();
+    let mut value = code.value.rotate_left(code.value.count_ones());
+    while value > 0 {
+        // Letters are encoded as 1 to 26; other digits only show up in made-up codes.
+        let letter = value % MULTIPLIER;
+        if !(1..=26).contains(&letter) {
+            return None;
+        }
+        s.push(char::from_u32('a' as u32 + letter - 1)?);
+        value /= MULTIPLIER;
+    }
+    Some(s)
+}
+
+fn encode_letter(c: char) -> u32 {
+    c as u32 - 'a' as u32 + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let word = "abbac";
+        assert_eq!(&decode(encode(word)).unwrap(), word);
+        let word = "tales";
+        assert_eq!(&decode(encode(word)).unwrap(), word);
+        let word = "hello";
+        assert_eq!(&decode(encode(word)).unwrap(), word);
+    }
+
+    #[test]
+    fn encode_uppercase_ends_up_lowercase() {
+        let word = "TALES";
+        assert_eq!(&decode(encode(word)).unwrap(), "tales");
+    }
+
+    #[test]
+    fn decode_rejects_codes_without_word() {
+        // 0 and 27 aren't letters
+        for digit in [0, 27] {
+            let value = encode_letter('a') * MULTIPLIER + digit;
+            let code = Code {
+                value: value.rotate_right(value.count_ones()),
+            };
+            assert_eq!(decode(code), None);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(word in "[a-zA-Z]{5}") {
+            prop_assert_eq!(decode(encode(&word)), Some(word.to_lowercase()));
+        }
+
+        #[test]
+        fn decode_encode_roundtrip(value: u32) {
+            let code = Code { value };
+            if let Some(word) = decode(code) {
+                prop_assert_eq!(encode(&word), code);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Evil,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Evil => "evil",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            "evil" => Ok(Difficulty::Evil),
+            _ => Err(()),
+        }
+    }
+}
+
+// The summed frequency of the five most common letters in the word list is slightly above this;
+// words made of common letters get a rarity score close to zero.
+const COMMON_LETTERS_FREQUENCY: f64 = 0.4;
+const RARITY_WEIGHT: f64 = 4.0;
+const REPEATED_LETTER_WEIGHT: f64 = 1.5;
+const NEIGHBOUR_WEIGHT: f64 = 1.0;
+
+// Score thresholds for medium, hard and evil; calibrated so that roughly a quarter of the
+// default word list ends up easy and only a few percent end up evil.
+const DIFFICULTY_THRESHOLDS: &[(f64, Difficulty)] = &[
+    (7.5, Difficulty::Evil),
+    (5.5, Difficulty::Hard),
+    (3.5, Difficulty::Medium),
+];
+
+/// Estimates how hard a word is to guess based on its letters and the word list it is played with.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DifficultyEstimator {
+    /// Relative frequency of each letter over all words in the word list.
+    letter_frequencies: HashMap<char, f64>,
+}
+
+impl DifficultyEstimator {
+    pub fn new(word_list: &HashSet<String>) -> Self {
+        let counts = itertools::Itertools::counts(word_list.iter().flat_map(|word| word.chars()));
+        let total: usize = counts.values().sum();
+        let letter_frequencies = counts
+            .into_iter()
+            .map(|(c, count)| (c, count as f64 / total.max(1) as f64))
+            .collect();
+        Self { letter_frequencies }
+    }
+
+    /// Words made of rare letters are harder to find.
+    fn rarity(&self, word: &str) -> f64 {
+        let unique_letters = word.chars().collect::<HashSet<_>>();
+        let frequency: f64 = unique_letters
+            .iter()
+            .map(|c| self.letter_frequencies.get(c).copied().unwrap_or_default())
+            .sum();
+        (1.0 - frequency / COMMON_LETTERS_FREQUENCY).max(0.0)
+    }
+
+    pub fn score(&self, word: &str, word_list: &HashSet<String>) -> f64 {
+        let repeated_letters = word.len() - word.chars().collect::<HashSet<_>>().len();
+        let neighbours = count_neighbours(word, word_list);
+
+        self.rarity(word) * RARITY_WEIGHT
+            + repeated_letters as f64 * REPEATED_LETTER_WEIGHT
+            + (1.0 + neighbours as f64).log2() * NEIGHBOUR_WEIGHT
+    }
+
+    pub fn estimate(&self, word: &str, word_list: &HashSet<String>) -> Difficulty {
+        let score = self.score(word, word_list);
+        DIFFICULTY_THRESHOLDS
+            .iter()
+            .find(|(threshold, _)| score >= *threshold)
+            .map(|(_, difficulty)| *difficulty)
+            .unwrap_or(Difficulty::Easy)
+    }
+}
+
+/// Counts the words in the word list that differ from `word` in exactly one position.
+/// Words with many such neighbours (e.g. "_atch") can't be solved by deduction alone.
+pub fn count_neighbours(word: &str, word_list: &HashSet<String>) -> usize {
+    let chars = word.chars().collect::<Vec<_>>();
+    let mut candidate = chars.clone();
+    let mut neighbours = 0;
+    for i in 0..chars.len() {
+        for c in 'a'..='z' {
+            if c == chars[i] {
+                continue;
+            }
+            candidate[i] = c;
+            if word_list.contains(&String::from_iter(&candidate)) {
+                neighbours += 1;
+            }
+        }
+        candidate[i] = chars[i];
+    }
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_list(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|word| String::from(*word)).collect()
+    }
+
+    #[test]
+    fn test_count_neighbours() {
+        let words = word_list(&["catch", "hatch", "latch", "match", "watch", "clown"]);
+        assert_eq!(count_neighbours("catch", &words), 4);
+        assert_eq!(count_neighbours("batch", &words), 5);
+        assert_eq!(count_neighbours("clown", &words), 0);
+    }
+
+    #[test]
+    fn test_neighbourhood_increases_difficulty() {
+        let words = word_list(&[
+            "catch", "hatch", "latch", "match", "watch", "patch", "crane",
+        ]);
+        let estimator = DifficultyEstimator::new(&words);
+        assert!(estimator.score("catch", &words) > estimator.score("crane", &words));
+    }
+
+    #[test]
+    fn test_repeated_letters_increase_difficulty() {
+        let words = word_list(&["abcde", "aabcd"]);
+        let estimator = DifficultyEstimator::new(&words);
+        assert!(estimator.score("aabcd", &words) > estimator.score("abcde", &words));
+    }
+
+    #[test]
+    fn test_difficulty_from_str() {
+        assert_eq!("Evil".parse(), Ok(Difficulty::Evil));
+        assert_eq!("easy".parse(), Ok(Difficulty::Easy));
+        assert_eq!("tricky".parse::<Difficulty>(), Err(()));
+        for difficulty in [
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Evil,
+        ] {
+            assert_eq!(difficulty.to_string().parse(), Ok(difficulty));
+        }
+    }
+}
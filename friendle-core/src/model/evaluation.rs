@@ -0,0 +1,422 @@
+use super::{guess_error::GuessError, validate_word::validate_word};
+use crate::constants::WORD_LENGTH;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum Evaluation {
+    Absent,
+    Present,
+    Correct,
+}
+
+impl Evaluation {
+    /// Plain word for the evaluation, e.g. for screen readers.
+    pub fn description(self) -> &'static str {
+        match self {
+            Evaluation::Absent => "absent",
+            Evaluation::Present => "present",
+            Evaluation::Correct => "correct",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum EmojiMode {
+    Unicode,
+    DiscordName,
+}
+
+impl EmojiMode {
+    pub const ALL: [EmojiMode; 2] = [EmojiMode::Unicode, EmojiMode::DiscordName];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EmojiMode::Unicode => "unicode",
+            EmojiMode::DiscordName => "names",
+        }
+    }
+}
+
+// We use red instead of Wordle's black/white since we have no way of knowing whether
+// the user uses Dark mode or Light mode.
+const EVALUATION_EMOTES: &[&str] = &["🟥", "🟨", "🟩"];
+const EVALUATION_EMOTE_DISCORD_NAMES: &[&str] =
+    &[":red_square:", ":yellow_square:", ":green_square:"];
+
+pub fn get_emoji(evaluation: Evaluation, emoji_mode: EmojiMode) -> &'static str {
+    match emoji_mode {
+        EmojiMode::Unicode => EVALUATION_EMOTES[evaluation as usize],
+        EmojiMode::DiscordName => EVALUATION_EMOTE_DISCORD_NAMES[evaluation as usize],
+    }
+}
+
+const NYT_EMOTES: &[&str] = &["⬛", "🟨", "🟩"];
+const NYT_EMOTE_DISCORD_NAMES: &[&str] =
+    &[":black_large_square:", ":yellow_square:", ":green_square:"];
+const COLORBLIND_EMOTES: &[&str] = &["⬛", "🟦", "🟧"];
+const COLORBLIND_EMOTE_DISCORD_NAMES: &[&str] =
+    &[":black_large_square:", ":blue_square:", ":orange_square:"];
+const SHAPES_EMOTES: &[&str] = &["❌", "⭕", "✅"];
+const SHAPES_EMOTE_DISCORD_NAMES: &[&str] = &[":x:", ":o:", ":white_check_mark:"];
+
+/// Emojis used to display evaluations.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EmojiTheme {
+    /// Red, yellow and green squares.
+    #[default]
+    Classic,
+    /// Black, yellow and green squares like the original Wordle.
+    Nyt,
+    /// Black, blue and orange squares like Wordle's high contrast mode.
+    Colorblind,
+    /// Crosses, circles and check marks that don't rely on color at all.
+    Shapes,
+    /// Custom (e.g. guild) emojis for absent, present and correct evaluations, in that order.
+    Custom([String; 3]),
+}
+
+impl EmojiTheme {
+    pub const BUILT_IN: [EmojiTheme; 4] = [
+        EmojiTheme::Classic,
+        EmojiTheme::Nyt,
+        EmojiTheme::Colorblind,
+        EmojiTheme::Shapes,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmojiTheme::Classic => "classic",
+            EmojiTheme::Nyt => "nyt",
+            EmojiTheme::Colorblind => "colorblind",
+            EmojiTheme::Shapes => "shapes",
+            EmojiTheme::Custom(_) => "custom",
+        }
+    }
+
+    pub fn emoji(&self, evaluation: Evaluation, emoji_mode: EmojiMode) -> &str {
+        let (emotes, discord_names) = match self {
+            EmojiTheme::Classic => return get_emoji(evaluation, emoji_mode),
+            EmojiTheme::Nyt => (NYT_EMOTES, NYT_EMOTE_DISCORD_NAMES),
+            EmojiTheme::Colorblind => (COLORBLIND_EMOTES, COLORBLIND_EMOTE_DISCORD_NAMES),
+            EmojiTheme::Shapes => (SHAPES_EMOTES, SHAPES_EMOTE_DISCORD_NAMES),
+            // Custom emojis look the same in both modes.
+            EmojiTheme::Custom(emojis) => return &emojis[evaluation as usize],
+        };
+        match emoji_mode {
+            EmojiMode::Unicode => emotes[evaluation as usize],
+            EmojiMode::DiscordName => discord_names[evaluation as usize],
+        }
+    }
+
+    /// Whether the theme shows Discord custom emojis, which only render as markup outside of messages.
+    pub fn uses_custom_emojis(&self) -> bool {
+        match self {
+            EmojiTheme::Custom(emojis) => emojis.iter().any(|emoji| is_custom_emoji(emoji)),
+            _ => false,
+        }
+    }
+
+    /// ANSI color code used for letters with the given evaluation in ANSI code blocks.
+    pub fn ansi_color(&self, evaluation: Evaluation) -> u8 {
+        // Discord supports gray (30), red (31), green (32), yellow (33), blue (34), pink (35), cyan (36) and white (37).
+        let colors = match self {
+            EmojiTheme::Nyt => [30, 33, 32],
+            // There is no orange, so we use yellow instead; blue and yellow are still easy to tell apart.
+            EmojiTheme::Colorblind => [30, 34, 33],
+            EmojiTheme::Classic | EmojiTheme::Shapes | EmojiTheme::Custom(_) => [31, 33, 32],
+        };
+        colors[evaluation as usize]
+    }
+
+    /// RGB color of board image tiles with the given evaluation.
+    pub fn tile_color(&self, evaluation: Evaluation) -> [u8; 3] {
+        let colors = match self {
+            EmojiTheme::Nyt => [[120, 124, 126], [201, 180, 88], [106, 170, 100]],
+            EmojiTheme::Colorblind => [[120, 124, 126], [133, 192, 249], [245, 121, 58]],
+            EmojiTheme::Classic | EmojiTheme::Shapes | EmojiTheme::Custom(_) => {
+                [[221, 46, 68], [253, 203, 88], [120, 177, 89]]
+            }
+        };
+        colors[evaluation as usize]
+    }
+}
+
+/// Whether `emoji` is a single Unicode emoji or a Discord custom emoji (`<:name:id>` or `<a:name:id>`),
+/// the only things a custom theme may show in a board cell.
+pub fn is_single_emoji(emoji: &str) -> bool {
+    is_custom_emoji(emoji) || is_unicode_emoji(emoji)
+}
+
+fn is_custom_emoji(emoji: &str) -> bool {
+    let Some(inner) = emoji.strip_prefix('<').and_then(|s| s.strip_suffix('>')) else {
+        return false;
+    };
+    let inner = inner.strip_prefix('a').unwrap_or(inner);
+    match inner.split(':').collect::<Vec<_>>()[..] {
+        ["", name, id] => {
+            (2..=32).contains(&name.len())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && (17..=20).contains(&id.len())
+                && id.chars().all(|c| c.is_ascii_digit())
+        }
+        _ => false,
+    }
+}
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const VARIATION_SELECTOR: char = '\u{FE0F}';
+const KEYCAP: char = '\u{20E3}';
+
+/// A single emoji, possibly a sequence of emojis joined into one, e.g. 👩‍👩‍👧, 👍🏽, 🇩🇪 or 1️⃣.
+fn is_unicode_emoji(emoji: &str) -> bool {
+    // The longest sequences (families with skin tones, subdivision flags) have a bit over 10 code points.
+    emoji.chars().count() <= 16
+        && emoji
+            .split(ZERO_WIDTH_JOINER)
+            .all(|component| is_emoji_component(&component.chars().collect::<Vec<_>>()))
+}
+
+fn is_emoji_component(chars: &[char]) -> bool {
+    let is_regional_indicator = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+    let is_modifier = |c: char| {
+        c == VARIATION_SELECTOR
+            // skin tones
+            || ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+            // tags of subdivision flags like 🏴󠁧󠁢󠁳󠁣󠁴󠁿
+            || ('\u{E0020}'..='\u{E007F}').contains(&c)
+    };
+    match chars {
+        // flags are made of two regional indicators
+        [a, b] if is_regional_indicator(*a) && is_regional_indicator(*b) => true,
+        // keycaps like 1️⃣
+        [digit, VARIATION_SELECTOR, KEYCAP] | [digit, KEYCAP] => {
+            digit.is_ascii_digit() || *digit == '#' || *digit == '*'
+        }
+        [base, modifiers @ ..] => is_pictograph(*base) && modifiers.iter().all(|c| is_modifier(*c)),
+        [] => false,
+    }
+}
+
+fn is_pictograph(c: char) -> bool {
+    matches!(c,
+        '\u{1F000}'..='\u{1FAFF}'
+        | '\u{2190}'..='\u{21FF}'
+        | '\u{2300}'..='\u{23FF}'
+        | '\u{25A0}'..='\u{27BF}'
+        | '\u{2900}'..='\u{297F}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{00A9}' | '\u{00AE}' | '\u{203C}' | '\u{2049}' | '\u{2122}' | '\u{2139}'
+        | '\u{3030}' | '\u{303D}' | '\u{3297}' | '\u{3299}'
+    ) && !('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+        && !('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+pub fn get_warning_emoji(emoji_mode: EmojiMode) -> &'static str {
+    match emoji_mode {
+        EmojiMode::Unicode => "⚠️",
+        EmojiMode::DiscordName => ":warning:",
+    }
+}
+
+pub fn evaluate(
+    guess: &str,
+    solution: &str,
+    word_list: &HashSet<String>,
+) -> Result<Vec<Evaluation>, GuessError> {
+    validate_word(guess, word_list, solution)?;
+    let chars_guess = guess.chars().collect::<Vec<_>>();
+    let chars_solution = solution.chars().collect::<Vec<_>>();
+    let mut evaluation = vec![Evaluation::Absent; WORD_LENGTH];
+    let mut solution_frequencies = itertools::Itertools::counts(solution.chars());
+
+    for i in 0..WORD_LENGTH {
+        if chars_guess[i] == chars_solution[i] {
+            evaluation[i] = Evaluation::Correct;
+            *solution_frequencies.get_mut(&chars_guess[i]).unwrap() -= 1;
+        }
+    }
+    for i in 0..WORD_LENGTH {
+        if chars_guess[i] != chars_solution[i] {
+            if let Some(val) = solution_frequencies.get_mut(&chars_guess[i]) {
+                if *val > 0 {
+                    evaluation[i] = Evaluation::Present;
+                    *val -= 1;
+                }
+            }
+        }
+    }
+
+    Ok(evaluation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_correct_eval() {
+        let word = "tales";
+        let word_list = HashSet::from_iter(std::iter::once(String::from(word)));
+        let eval = evaluate(word, word, &word_list).unwrap();
+        assert_eq!(eval, vec![Evaluation::Correct; WORD_LENGTH]);
+    }
+
+    #[test]
+    fn test_word_invalid_len() {
+        let word = "abcdef";
+        let solution = "abcde";
+        let word_list = HashSet::from_iter(std::iter::once(String::from(word)));
+        assert!(evaluate(word, solution, &word_list).is_err());
+    }
+
+    #[test]
+    fn test_word_invalid_letter() {
+        let word = "naïve";
+        let solution = "abcde";
+        let word_list = HashSet::from_iter(std::iter::once(String::from(word)));
+        assert!(evaluate(word, solution, &word_list).is_err());
+    }
+
+    #[test]
+    fn test_partial_eval() {
+        let word = "abbac";
+        let solution = "bdaab";
+        let word_list = HashSet::from_iter(std::iter::once(String::from(word)));
+        let eval = evaluate(word, solution, &word_list).unwrap();
+        assert_eq!(
+            &eval,
+            &[
+                Evaluation::Present,
+                Evaluation::Present,
+                Evaluation::Present,
+                Evaluation::Correct,
+                Evaluation::Absent,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_letter_frequencies() {
+        let word = "abbac";
+        let freq = itertools::Itertools::counts(word.chars());
+        let mut frequencies_vec = freq.into_iter().collect::<Vec<_>>();
+        frequencies_vec.sort_by_key(|(a, _)| *a);
+        assert_eq!(frequencies_vec, vec![('a', 2usize), ('b', 2), ('c', 1)]);
+    }
+
+    #[test]
+    fn test_theme_emoji() {
+        assert_eq!(
+            EmojiTheme::Classic.emoji(Evaluation::Absent, EmojiMode::Unicode),
+            "🟥"
+        );
+        assert_eq!(
+            EmojiTheme::Nyt.emoji(Evaluation::Absent, EmojiMode::DiscordName),
+            ":black_large_square:"
+        );
+        assert_eq!(
+            EmojiTheme::Colorblind.emoji(Evaluation::Correct, EmojiMode::Unicode),
+            "🟧"
+        );
+        assert_eq!(
+            EmojiTheme::Shapes.emoji(Evaluation::Present, EmojiMode::DiscordName),
+            ":o:"
+        );
+        let custom = EmojiTheme::Custom([
+            String::from("<:no:1>"),
+            String::from("<:maybe:2>"),
+            String::from("<:yes:3>"),
+        ]);
+        assert_eq!(
+            custom.emoji(Evaluation::Present, EmojiMode::Unicode),
+            "<:maybe:2>"
+        );
+        assert_eq!(
+            custom.emoji(Evaluation::Correct, EmojiMode::DiscordName),
+            "<:yes:3>"
+        );
+    }
+
+    #[test]
+    fn test_evaluation_emoji() {
+        assert_eq!(get_emoji(Evaluation::Absent, EmojiMode::Unicode), "🟥");
+        assert_eq!(
+            get_emoji(Evaluation::Absent, EmojiMode::DiscordName),
+            ":red_square:"
+        );
+
+        assert_eq!(get_emoji(Evaluation::Present, EmojiMode::Unicode), "🟨");
+        assert_eq!(
+            get_emoji(Evaluation::Present, EmojiMode::DiscordName),
+            ":yellow_square:"
+        );
+
+        assert_eq!(get_emoji(Evaluation::Correct, EmojiMode::Unicode), "🟩");
+        assert_eq!(
+            get_emoji(Evaluation::Correct, EmojiMode::DiscordName),
+            ":green_square:"
+        );
+    }
+
+    proptest! {
+        // A small alphabet, so guesses and solutions share (repeated) letters most of the time.
+        #[test]
+        fn evaluation_respects_letter_counts(guess in "[a-f]{5}", solution in "[a-f]{5}") {
+            let word_list = HashSet::from_iter(std::iter::once(guess.clone()));
+            let eval = evaluate(&guess, &solution, &word_list).unwrap();
+            let solution_counts = itertools::Itertools::counts(solution.chars());
+            for (c, count) in itertools::Itertools::counts(guess.chars()) {
+                let marked = guess
+                    .chars()
+                    .zip(&eval)
+                    .filter(|(g, e)| *g == c && **e != Evaluation::Absent)
+                    .count();
+                let in_solution = solution_counts.get(&c).copied().unwrap_or(0);
+                // every letter is marked as often as it's in the solution, but never more often
+                prop_assert_eq!(marked, count.min(in_solution));
+            }
+            for ((g, s), e) in guess.chars().zip(solution.chars()).zip(&eval) {
+                prop_assert_eq!(g == s, *e == Evaluation::Correct);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_emoji() {
+        for emoji in [
+            "🟥",
+            "⬛",
+            "❌",
+            "✅",
+            "⭕",
+            "❤️",
+            "👍🏽",
+            "👩‍👩‍👧",
+            "🇩🇪",
+            "1️⃣",
+            "<:no:123456789012345678>",
+            "<a:party_time:123456789012345678>",
+        ] {
+            assert!(is_single_emoji(emoji), "{emoji}");
+        }
+        for not_emoji in [
+            "",
+            "x",
+            "abc",
+            "🟥🟥",
+            "🇩🇪🇫🇷",
+            "🟥x",
+            "1",
+            "<:no:>",
+            "<:no:12>",
+            "<:a b:123456789012345678>",
+            "<:no:123456789012345678>🟥",
+            "@everyone",
+        ] {
+            assert!(!is_single_emoji(not_emoji), "{not_emoji}");
+        }
+        assert!(!is_single_emoji(&"🟥".repeat(100)));
+    }
+}
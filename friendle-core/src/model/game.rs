@@ -0,0 +1,633 @@
+use super::coding::Code;
+use super::evaluation::{evaluate, get_warning_emoji, EmojiMode, EmojiTheme, Evaluation};
+use super::guess_error::GuessError;
+use super::knowledge::{Knowledge, KnowledgeValidationError};
+use super::suggestions::suggest_words;
+use super::validate_word::validate_word_format;
+use crate::constants::{self, MAX_GUESSES};
+use crate::i18n::{GameMessage, Locale, Localize};
+use crate::keyboard::{get_regional_indicator_emoji_with_zero_width_space, KeyboardLayout};
+use crate::render::Renderer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guess {
+    pub word: String,
+    pub evaluation: Vec<Evaluation>,
+    /// Set if the guess contradicted what was already known; only recorded in soft strict mode.
+    pub warning: Option<KnowledgeValidationError>,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum GameState {
+    InProgress,
+    Won,
+    Lost,
+}
+
+/// Strict modes, ordered from most lenient to strictest.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum StrictMode {
+    #[default]
+    Disabled,
+    /// Guesses contradicting what is already known are accepted, but counted as wasted.
+    Warn,
+    /// NYT hard mode: correct letters must stay in place and present letters must be reused.
+    NytHard,
+    /// Friendle strict mode: guesses contradicting what is already known are rejected.
+    Enabled,
+}
+
+impl StrictMode {
+    pub const ALL: [StrictMode; 4] = [
+        StrictMode::Disabled,
+        StrictMode::Warn,
+        StrictMode::NytHard,
+        StrictMode::Enabled,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StrictMode::Disabled => "off",
+            StrictMode::Warn => "soft",
+            StrictMode::NytHard => "nyt",
+            StrictMode::Enabled => "strict",
+        }
+    }
+
+    /// Parses a mode by its name, as shown by `name`.
+    pub fn from_name(name: &str) -> Option<StrictMode> {
+        StrictMode::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Marker shown next to the guess count in the game state header.
+    pub fn marker(self) -> &'static str {
+        match self {
+            StrictMode::Disabled => " ",
+            StrictMode::Warn => "~",
+            StrictMode::NytHard => "*",
+            StrictMode::Enabled => "**",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ModeChangeError {
+    AlreadySet,
+    TooManyGuessesAlready,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum GameFlag {
+    SolutionNotInWordList,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameFlags(HashSet<GameFlag>);
+
+impl Deref for GameFlags {
+    type Target = HashSet<GameFlag>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for GameFlags {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    code: Code,
+    flags: GameFlags,
+    strict_mode: StrictMode,
+    solution: String,
+    state: GameState,
+    history: Vec<Guess>,
+    knowledge: Knowledge,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+pub enum LetterState {
+    Unknown,
+    Absent,
+    Present,
+    Correct,
+}
+
+impl From<Evaluation> for LetterState {
+    fn from(eval: Evaluation) -> Self {
+        match eval {
+            Evaluation::Absent => LetterState::Absent,
+            Evaluation::Present => LetterState::Present,
+            Evaluation::Correct => LetterState::Correct,
+        }
+    }
+}
+
+impl LetterState {
+    pub fn to_evaluation(self) -> Option<Evaluation> {
+        match self {
+            LetterState::Unknown => None,
+            LetterState::Absent => Some(Evaluation::Absent),
+            LetterState::Present => Some(Evaluation::Present),
+            LetterState::Correct => Some(Evaluation::Correct),
+        }
+    }
+}
+
+impl Game {
+    pub fn new(
+        code: Code,
+        solution: String,
+        word_list: &HashSet<String>,
+    ) -> Result<Self, GuessError> {
+        let word_length = solution.len();
+        validate_word_format(&solution)?;
+        let mut flags = GameFlags::default();
+
+        if !word_list.contains(&solution) {
+            flags.0.insert(GameFlag::SolutionNotInWordList);
+        }
+
+        Ok(Self {
+            code,
+            solution,
+            history: vec![],
+            state: GameState::InProgress,
+            flags,
+            strict_mode: StrictMode::Disabled,
+            knowledge: Knowledge::new(word_length),
+        })
+    }
+
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    pub fn flags(&self) -> &GameFlags {
+        &self.flags
+    }
+
+    pub fn solution(&self) -> &str {
+        &self.solution
+    }
+
+    pub fn knowledge(&self) -> &Knowledge {
+        &self.knowledge
+    }
+
+    pub fn history(&self) -> &[Guess] {
+        &self.history
+    }
+
+    pub fn can_switch_to_mode(&self, mode: StrictMode) -> Result<(), ModeChangeError> {
+        if mode == self.get_strict_mode() {
+            return Err(ModeChangeError::AlreadySet);
+        }
+
+        if mode < self.get_strict_mode() {
+            // switching to a more lenient mode is always possible
+            Ok(())
+        } else if self.history.len() <= 1 {
+            // switching to a stricter mode is only possible until we're one guess in.
+            Ok(())
+        } else {
+            Err(ModeChangeError::TooManyGuessesAlready)
+        }
+    }
+
+    /// All modes the game can currently be switched to.
+    pub fn mode_switch_options(&self) -> Vec<StrictMode> {
+        StrictMode::ALL
+            .into_iter()
+            .filter(|mode| self.can_switch_to_mode(*mode).is_ok())
+            .collect()
+    }
+
+    pub fn get_strict_mode(&self) -> StrictMode {
+        self.strict_mode
+    }
+
+    pub fn set_strict_mode(&mut self, mode: StrictMode) -> Result<(), ModeChangeError> {
+        self.can_switch_to_mode(mode)?;
+        self.strict_mode = mode;
+        Ok(())
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn guess(
+        &mut self,
+        guessed_word: String,
+        word_list: &HashSet<String>,
+    ) -> Result<(), GuessError> {
+        if self.state != GameState::InProgress {
+            return Err(GuessError::GameNotInProgress);
+        }
+        let evaluation = evaluate(&guessed_word, &self.solution, word_list)?;
+        let validation = self.validate_rules(&guessed_word);
+        let warning = match self.strict_mode {
+            StrictMode::Warn => validation.err(),
+            _ => {
+                validation?;
+                None
+            }
+        };
+        let guess_eval = Guess {
+            word: guessed_word,
+            evaluation,
+            warning,
+        };
+        self.knowledge.learn(&guess_eval);
+
+        if guess_eval
+            .evaluation
+            .iter()
+            .all(|eval| *eval == Evaluation::Correct)
+        {
+            self.state = GameState::Won;
+        }
+        self.history.push(guess_eval);
+        if self.state != GameState::Won && self.history.len() == MAX_GUESSES {
+            self.state = GameState::Lost;
+        }
+        Ok(())
+    }
+
+    /// Validates a word against the rules of the current strict mode.
+    /// Soft strict mode uses the same rules as Friendle strict mode.
+    fn validate_rules(&self, word: &str) -> Result<(), KnowledgeValidationError> {
+        match self.strict_mode {
+            StrictMode::Disabled => Ok(()),
+            StrictMode::NytHard => self.knowledge.validate_nyt_hard(word),
+            StrictMode::Warn | StrictMode::Enabled => self.knowledge.validate(word),
+        }
+    }
+
+    /// Number of guesses that contradicted what was already known, as recorded in soft strict mode.
+    pub fn wasted_guesses(&self) -> usize {
+        self.history
+            .iter()
+            .filter(|guess| guess.warning.is_some())
+            .count()
+    }
+
+    fn strict_mode_star(&self) -> &str {
+        self.get_strict_mode().marker()
+    }
+
+    fn wasted_guesses_summary(&self, locale: Locale) -> String {
+        if self.get_strict_mode() == StrictMode::Warn {
+            format!(
+                " {}",
+                GameMessage::WastedGuesses(self.wasted_guesses()).localize(locale)
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn display_game_state_header(&self, renderer: &mut impl Renderer, locale: Locale) {
+        let code = self.code.value;
+        match self.state {
+            GameState::InProgress => {
+                renderer.push_line(&format!("Friendle `{code}`"));
+                renderer.push(&format!(
+                    "{}/{}{}{} {}",
+                    self.history().len(),
+                    constants::MAX_GUESSES,
+                    self.strict_mode_star(),
+                    self.wasted_guesses_summary(locale),
+                    GameMessage::InProgress.localize(locale),
+                ));
+
+                if self.flags().contains(&GameFlag::SolutionNotInWordList) {
+                    renderer.push(&format!(" {}", GameMessage::NotInWordList.localize(locale)));
+                }
+                renderer.push_line("");
+            }
+            GameState::Won => {
+                let line = format!(
+                    "{}/{}{}{}",
+                    self.history().len(),
+                    constants::MAX_GUESSES,
+                    self.strict_mode_star(),
+                    self.wasted_guesses_summary(locale),
+                );
+                renderer.push_line(&format!("Friendle `{code}`: {line}"));
+            }
+            GameState::Lost => {
+                let line = format!(
+                    "X/{}{}{}",
+                    constants::MAX_GUESSES,
+                    self.strict_mode_star(),
+                    self.wasted_guesses_summary(locale),
+                );
+                renderer.push_line(&format!("Friendle `{code}`: {line}"));
+            }
+        }
+    }
+
+    pub fn display_state(
+        &self,
+        renderer: &mut impl Renderer,
+        emoji_mode: EmojiMode,
+        theme: &EmojiTheme,
+    ) {
+        for guess in &self.history {
+            if self.state == GameState::InProgress {
+                // guessed word converted to emojis
+                renderer.push_line(&String::from_iter(
+                    guess
+                        .word
+                        .chars()
+                        .map(get_regional_indicator_emoji_with_zero_width_space),
+                ));
+            }
+            // evaluation converted to emojis
+            renderer.push(&String::from_iter(
+                guess
+                    .evaluation
+                    .iter()
+                    .map(|eval| theme.emoji(*eval, emoji_mode).to_string()),
+            ));
+            if guess.warning.is_some() {
+                // mark wasted guesses
+                renderer.push(&format!(" {}", get_warning_emoji(emoji_mode)));
+            }
+            renderer.push_line("");
+            if self.state == GameState::InProgress {
+                renderer.push_line("");
+            }
+        }
+    }
+
+    /// Like `display_state`, but as plain sentences a screen reader can read out,
+    /// e.g. "Guess 1: T correct, A present, L absent, E absent, S absent."
+    pub fn display_state_as_text(&self, renderer: &mut impl Renderer, locale: Locale) {
+        for (index, guess) in self.history.iter().enumerate() {
+            let letters = guess
+                .word
+                .chars()
+                .zip(&guess.evaluation)
+                .map(|(letter, evaluation)| {
+                    if self.state == GameState::InProgress {
+                        format!(
+                            "{} {}",
+                            letter.to_ascii_uppercase(),
+                            evaluation.localize(locale)
+                        )
+                    } else {
+                        // don't spoil the guessed words in shared results
+                        evaluation.localize(locale)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            renderer.push(&format!(
+                "{} {letters}.",
+                GameMessage::GuessNumber(index + 1).localize(locale)
+            ));
+            if guess.warning.is_some() {
+                renderer.push(&format!(
+                    " {}",
+                    GameMessage::CountsAsWasted.localize(locale)
+                ));
+            }
+            renderer.push_line("");
+        }
+    }
+
+    /// Words from the word list that are close to the given (rejected) guess.
+    /// In strict modes, only words allowed by the rules of the current mode are suggested.
+    pub fn suggestions(
+        &self,
+        guessed_word: &str,
+        word_list: &HashSet<String>,
+        layout: KeyboardLayout,
+    ) -> Vec<String> {
+        suggest_words(guessed_word, word_list, layout, |candidate| {
+            self.validate_rules(candidate).is_ok()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::knowledge::CharacterBound;
+
+    #[test]
+    fn set_strict_mode() {
+        let solution = String::from("tales");
+
+        let mut word_list = HashSet::new();
+        word_list.insert(String::from("earth"));
+        word_list.insert(String::from("value"));
+        word_list.insert(String::from("slime"));
+
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+
+        for _ in 0..2 {
+            // try changing modes. We do two iterations with one guess in between.
+            // We start in non-strict mode
+            assert_eq!(game.get_strict_mode(), StrictMode::Disabled);
+            assert_eq!(
+                game.set_strict_mode(StrictMode::Disabled),
+                Err(ModeChangeError::AlreadySet)
+            );
+
+            // setting strict mode succeeds
+            assert_eq!(game.set_strict_mode(StrictMode::Enabled), Ok(()));
+            // setting the same mode again fails
+            assert_eq!(
+                game.set_strict_mode(StrictMode::Enabled),
+                Err(ModeChangeError::AlreadySet)
+            );
+            // going back to non-strict mode always works.
+            assert_eq!(game.set_strict_mode(StrictMode::Disabled), Ok(()));
+
+            game.guess(String::from("earth"), &word_list).unwrap();
+        }
+
+        assert_eq!(
+            game.set_strict_mode(StrictMode::Enabled),
+            Err(ModeChangeError::TooManyGuessesAlready)
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_from_name() {
+        for mode in StrictMode::ALL {
+            assert_eq!(StrictMode::from_name(mode.name()), Some(mode));
+        }
+        assert_eq!(StrictMode::from_name("NYT"), Some(StrictMode::NytHard));
+        assert_eq!(StrictMode::from_name("hard"), None);
+    }
+
+    #[test]
+    fn test_mode_switch_options() {
+        let solution = String::from("tales");
+        let word_list = HashSet::from_iter(["earth", "value"].map(String::from));
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+
+        assert_eq!(
+            game.mode_switch_options(),
+            vec![StrictMode::Warn, StrictMode::NytHard, StrictMode::Enabled]
+        );
+        game.set_strict_mode(StrictMode::Warn).unwrap();
+        assert_eq!(
+            game.mode_switch_options(),
+            vec![
+                StrictMode::Disabled,
+                StrictMode::NytHard,
+                StrictMode::Enabled
+            ]
+        );
+
+        game.guess(String::from("earth"), &word_list).unwrap();
+        game.guess(String::from("value"), &word_list).unwrap();
+        // stricter modes are no longer available, but going back to non-strict mode is.
+        assert_eq!(game.mode_switch_options(), vec![StrictMode::Disabled]);
+    }
+
+    #[test]
+    fn test_soft_strict_mode_counts_wasted_guesses() {
+        let solution = String::from("tales");
+        let word_list = HashSet::from_iter(["value", "slime"].map(String::from));
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+        game.set_strict_mode(StrictMode::Warn).unwrap();
+
+        game.guess(String::from("value"), &word_list).unwrap();
+        assert_eq!(game.wasted_guesses(), 0);
+
+        // "slime" doesn't contain the `a` we know about, but is still accepted.
+        game.guess(String::from("slime"), &word_list).unwrap();
+        assert_eq!(game.history().len(), 2);
+        assert_eq!(game.wasted_guesses(), 1);
+        assert!(matches!(
+            game.history()[1].warning,
+            Some(KnowledgeValidationError::WrongCount { character: 'a', .. })
+        ));
+
+        let mut header = String::new();
+        game.display_game_state_header(&mut header, Locale::En);
+        assert!(header.contains("2/6~ (1 wasted) [in progress]"));
+
+        let mut text = String::new();
+        game.display_state_as_text(&mut text, Locale::En);
+        assert_eq!(
+            text,
+            "Guess 1: V absent, A correct, L correct, U absent, E present.\n\
+             Guess 2: S present, L present, I absent, M absent, E present. Counts as wasted.\n"
+        );
+
+        let mut text = String::new();
+        game.display_state_as_text(&mut text, Locale::De);
+        assert!(
+            text.starts_with("Versuch 1: V fehlt, A richtig, L richtig, U fehlt, E vorhanden.\n")
+        );
+    }
+
+    #[test]
+    fn test_nyt_hard_mode_is_more_lenient_than_strict_mode() {
+        let solution = String::from("schwa");
+        let word_list = HashSet::from_iter(["scant", "scald", "scold"].map(String::from));
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+        game.set_strict_mode(StrictMode::NytHard).unwrap();
+        game.guess(String::from("scant"), &word_list).unwrap();
+
+        // Reusing the present `a` in the same position is fine in NYT hard mode...
+        let mut strict_game = game.clone();
+        game.guess(String::from("scald"), &word_list).unwrap();
+        // ...but not in Friendle strict mode.
+        strict_game.strict_mode = StrictMode::Enabled;
+        assert!(strict_game
+            .guess(String::from("scald"), &word_list)
+            .is_err());
+
+        // Dropping the present `a` is rejected in NYT hard mode.
+        let err = game.guess(String::from("scold"), &word_list).unwrap_err();
+        assert_eq!(
+            err,
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::WrongCount {
+                character: 'a',
+                given_count: 0,
+                bound: CharacterBound::Minimum(1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_reports_absent_letters() {
+        let solution = String::from("tales");
+        let word_list = HashSet::from_iter(["value"].map(String::from));
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+        game.set_strict_mode(StrictMode::Enabled).unwrap();
+        game.guess(String::from("value"), &word_list).unwrap();
+
+        let err = game.guess(String::from("value"), &word_list).unwrap_err();
+        assert_eq!(
+            err,
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::AbsentLetter {
+                character: 'v'
+            })
+        );
+    }
+
+    #[test]
+    fn test_win() {
+        let word = String::from("tales");
+        let word_list = HashSet::from_iter(std::iter::once(word.clone()));
+        let mut game = Game::new(Code { value: 1234 }, word.clone(), &word_list).unwrap(); // not the real code, but it doesn't matter here since it's only used for reporting
+        game.guess(word.clone(), &word_list).unwrap();
+        assert_eq!(game.state, GameState::Won);
+        assert!(game.guess(word, &word_list).is_err());
+    }
+
+    #[test]
+    fn test_knowledge_letter_state() {
+        let solution = String::from("tales");
+
+        let mut word_list = HashSet::new();
+        word_list.insert(String::from("earth"));
+        word_list.insert(String::from("value"));
+        word_list.insert(String::from("slime"));
+
+        let mut game = Game::new(Code { value: 1234 }, solution, &word_list).unwrap();
+        for word in &word_list {
+            game.guess(word.clone(), &word_list).unwrap();
+        }
+
+        assert!(game.flags().contains(&GameFlag::SolutionNotInWordList));
+
+        assert_eq!(LetterState::Present, game.knowledge().letter_state('t'));
+        assert_eq!(LetterState::Correct, game.knowledge().letter_state('a'));
+        assert_eq!(LetterState::Correct, game.knowledge().letter_state('l'));
+        assert_eq!(LetterState::Present, game.knowledge().letter_state('e'));
+        assert_eq!(LetterState::Present, game.knowledge().letter_state('s'));
+
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('r'));
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('h'));
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('v'));
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('u'));
+
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('m'));
+        assert_eq!(LetterState::Absent, game.knowledge().letter_state('i'));
+
+        assert_eq!(LetterState::Unknown, game.knowledge().letter_state('w'));
+        assert_eq!(LetterState::Unknown, game.knowledge().letter_state('x'));
+        assert_eq!(LetterState::Unknown, game.knowledge().letter_state('y'));
+        assert_eq!(LetterState::Unknown, game.knowledge().letter_state('z'));
+    }
+}
@@ -1,18 +1,23 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
 };
 use thiserror::Error;
 
-use super::{evaluation::Evaluation, game::Guess};
+use super::{
+    evaluation::Evaluation,
+    game::{Guess, LetterState},
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionalKnowledge {
     index: usize,
     knowledge_state: PositionalKnowledgeState,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PositionalKnowledgeState {
     /// The correct character for this position has been discovered.
     FixedLetter(char),
@@ -29,6 +34,14 @@ impl PositionalKnowledge {
         }
     }
 
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn state(&self) -> &PositionalKnowledgeState {
+        &self.knowledge_state
+    }
+
     pub fn validate(&self, c: char) -> Result<(), KnowledgeValidationError> {
         match &self.knowledge_state {
             PositionalKnowledgeState::FixedLetter(fixed_char) => {
@@ -56,20 +69,20 @@ impl PositionalKnowledge {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CharacterBound {
     Minimum(usize),
     Exact(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Knowledge {
     // both for absent characters as well as character limits.
     known_character_bounds: HashMap<char, CharacterBound>,
     positional_knowledge: Vec<PositionalKnowledge>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
 pub enum KnowledgeValidationError {
     #[error(
         "Letter in position {} is known to be `{correct_character}` but `{given_character}` was given.", position + 1
@@ -87,6 +100,8 @@ pub enum KnowledgeValidationError {
         given_count: usize,
         bound: CharacterBound,
     },
+    #[error("Letter `{character}` is known to not be in the word.")]
+    AbsentLetter { character: char },
 }
 
 impl CharacterBound {
@@ -108,10 +123,15 @@ impl CharacterBound {
         }
     }
 
+    pub fn count(&self) -> usize {
+        match self {
+            CharacterBound::Minimum(count) | CharacterBound::Exact(count) => *count,
+        }
+    }
+
+    /// Describes the bound without the trailing punctuation used in error messages, e.g. "at least 2".
     fn count_string(&self) -> String {
-        let count = match self {
-            CharacterBound::Minimum(count) | CharacterBound::Exact(count) => count,
-        };
+        let count = self.count();
 
         match count {
             1 => String::from("once"),
@@ -177,23 +197,111 @@ impl Knowledge {
         }
     }
 
-    pub fn add(&mut self, guess: &Guess) -> Result<(), KnowledgeValidationError> {
-        let guess_character_frequency = itertools::Itertools::counts(guess.word.chars());
-        let characters_in_guess = guess.word.chars().collect::<Vec<_>>();
+    pub fn positions(&self) -> &[PositionalKnowledge] {
+        &self.positional_knowledge
+    }
+
+    pub fn character_bound(&self, c: char) -> Option<CharacterBound> {
+        self.known_character_bounds.get(&c).copied()
+    }
+
+    /// The most specific state known for a letter anywhere in the word.
+    pub fn letter_state(&self, letter: char) -> LetterState {
+        let letter = letter.to_ascii_lowercase();
+        let is_fixed = self.positional_knowledge.iter().any(|position| {
+            matches!(position.state(), PositionalKnowledgeState::FixedLetter(c) if *c == letter)
+        });
+        if is_fixed {
+            return LetterState::Correct;
+        }
+        match self.character_bound(letter) {
+            None => LetterState::Unknown,
+            Some(bound) if bound.count() == 0 => LetterState::Absent,
+            Some(_) => LetterState::Present,
+        }
+    }
+
+    /// Letters that are known to occur at least once, sorted alphabetically.
+    pub fn required_letters(&self) -> Vec<(char, CharacterBound)> {
+        self.known_character_bounds
+            .iter()
+            .filter(|(_, bound)| bound.count() > 0)
+            .map(|(c, bound)| (*c, *bound))
+            .sorted()
+            .collect()
+    }
 
-        // Validate all known per-character limits
-        for (character, bound) in self.known_character_bounds.iter() {
-            let count_given = *guess_character_frequency.get(character).unwrap_or(&0);
+    /// Letters that are known to not occur at all, sorted alphabetically.
+    pub fn absent_letters(&self) -> Vec<char> {
+        self.known_character_bounds
+            .iter()
+            .filter(|(_, bound)| **bound == CharacterBound::Exact(0))
+            .map(|(c, _)| *c)
+            .sorted()
+            .collect()
+    }
+
+    /// Checks whether `word` is consistent with everything known so far.
+    /// Letters known to be absent are reported before anything else.
+    pub fn validate(&self, word: &str) -> Result<(), KnowledgeValidationError> {
+        if let Some(character) = word
+            .chars()
+            .find(|c| self.known_character_bounds.get(c) == Some(&CharacterBound::Exact(0)))
+        {
+            return Err(KnowledgeValidationError::AbsentLetter { character });
+        }
+
+        let character_frequency = itertools::Itertools::counts(word.chars());
+
+        // Validate all known per-character limits, sorted to report errors deterministically
+        for (character, bound) in self
+            .known_character_bounds
+            .iter()
+            .sorted_by_key(|(c, _)| **c)
+        {
+            let count_given = *character_frequency.get(character).unwrap_or(&0);
             bound.validate_count(*character, count_given)?;
         }
 
         // Check each position for known fixed or incorrect characters
-        for (i, c) in characters_in_guess.iter().enumerate() {
+        for (i, c) in word.chars().enumerate() {
             let positional_knowledge = &self.positional_knowledge[i];
-            positional_knowledge.validate(*c)?;
+            positional_knowledge.validate(c)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks `word` against NYT hard mode rules:
+    /// correct letters must stay in place, and present letters must be used again.
+    pub fn validate_nyt_hard(&self, word: &str) -> Result<(), KnowledgeValidationError> {
+        let characters = word.chars().collect::<Vec<_>>();
+        for positional_knowledge in &self.positional_knowledge {
+            if let PositionalKnowledgeState::FixedLetter(_) = positional_knowledge.knowledge_state {
+                positional_knowledge.validate(characters[positional_knowledge.index])?;
+            }
         }
 
-        // No validation errors past this point.
+        let character_frequency = itertools::Itertools::counts(word.chars());
+        // Sorted to report missing letters in a deterministic order.
+        for (character, bound) in self
+            .known_character_bounds
+            .iter()
+            .sorted_by_key(|(c, _)| **c)
+        {
+            CharacterBound::Minimum(bound.count()).validate_count(
+                *character,
+                *character_frequency.get(character).unwrap_or(&0),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Updates the knowledge with the evaluation of a guess without validating the guess first.
+    /// Evaluations always reflect the solution, so this never loses information,
+    /// even for guesses that contradict what was already known.
+    pub fn learn(&mut self, guess: &Guess) {
+        let characters_in_guess = guess.word.chars().collect::<Vec<_>>();
 
         // Update character limits
         // We can identify exact character bounds only through "Absent" evaluations.
@@ -236,32 +344,26 @@ impl Knowledge {
                 Evaluation::Present | Evaluation::Absent => {
                     // Absent evaluations (black Wordle characters) still go here; the character might appear multiple times and be present in another position.
                     // Character limits are handled outside of positional knowledge.
+                    // If the position is already fixed, there is nothing new to learn here.
                     if let PositionalKnowledgeState::IncorrectLetters(incorrect_letters) =
                         &mut self.positional_knowledge[i].knowledge_state
                     {
                         incorrect_letters.insert(c);
-                    } else {
-                        eprintln!(
-                            "Incorrect letter {} added to position knowledge ,
-                            but the knowledge state doesn't have a set of incorrect letters: {:?}.
-                            This means Knowledge::add() shouldn't be called, and validation should catch this.",
-                            c, self.positional_knowledge[i]
-                        )
                     }
                 }
             }
         }
-
-        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::model::{
+        evaluation::evaluate,
         game::{Game, GameState, StrictMode},
         guess_error::GuessError,
     };
+    use proptest::prelude::*;
 
     use super::*;
 
@@ -306,17 +408,14 @@ mod tests {
 
         // guessing the same word again fails
         let err = game.guess(String::from("value"), &word_list).unwrap_err();
-        assert!(matches!(
+        assert_eq!(
             err,
-            GuessError::RejectedByStrictMode(KnowledgeValidationError::WrongCount {
-                // We can't assert_eq here because we'll get a wrong count for one of the letters that we know is incorrect: either 'v' or 'u'
-                // Which letter we get depends on HashMap iteration order, which is nondeterministic.
-                // All other letters are in the solution, and counts are checked before positional knowledge, so we can have one of two errors here:
-                character: 'v' | 'u',
-                given_count: 1,
-                bound: CharacterBound::Exact(0)
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::AbsentLetter {
+                // Both 'v' and 'u' are known to be absent; absent letters are reported first,
+                // in the order they appear in the guess.
+                character: 'v'
             })
-        ));
+        );
 
         // guesses rejected by strict mode validation aren't counted
         assert_eq!(game.history().len(), 1);
@@ -373,6 +472,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_letter_state_from_single_guess() {
+        let mut knowledge = Knowledge::new(5);
+        knowledge.learn(&Guess {
+            word: String::from("abcbc"),
+            evaluation: vec![
+                Evaluation::Absent,
+                Evaluation::Absent,
+                Evaluation::Absent,
+                Evaluation::Present,
+                Evaluation::Correct,
+            ],
+            warning: None,
+        });
+
+        assert_eq!(LetterState::Absent, knowledge.letter_state('a'));
+        assert_eq!(LetterState::Present, knowledge.letter_state('b'));
+        assert_eq!(LetterState::Correct, knowledge.letter_state('c'));
+        assert_eq!(LetterState::Unknown, knowledge.letter_state('d'));
+        assert_eq!(
+            knowledge.character_bound('b'),
+            Some(CharacterBound::Exact(1))
+        );
+    }
+
+    #[test]
+    fn test_knowledge_accessors() -> anyhow::Result<()> {
+        let word = "tales";
+        let word_list = HashSet::from_iter(["value"].map(String::from));
+        let mut game = Game::new(
+            crate::model::coding::encode(word),
+            String::from(word),
+            &word_list,
+        )?;
+        game.guess(String::from("value"), &word_list)?;
+
+        let knowledge = game.knowledge();
+        assert_eq!(knowledge.absent_letters(), vec!['u', 'v']);
+        assert_eq!(
+            knowledge.required_letters(),
+            vec![
+                ('a', CharacterBound::Minimum(1)),
+                ('e', CharacterBound::Minimum(1)),
+                ('l', CharacterBound::Minimum(1)),
+            ]
+        );
+        assert!(matches!(
+            knowledge.positions()[1].state(),
+            PositionalKnowledgeState::FixedLetter('a')
+        ));
+        assert!(matches!(
+            knowledge.positions()[4].state(),
+            PositionalKnowledgeState::IncorrectLetters(letters) if letters.contains(&'e')
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_character_limits_duplicates() -> anyhow::Result<()> {
         let word = "fates";
@@ -392,10 +548,12 @@ mod tests {
 
         // Trying the same guess again fails:
         let err = game.guess(first_guess, &word_list).unwrap_err();
-        assert!(matches!(
+        assert_eq!(
             err,
-            GuessError::RejectedByStrictMode(KnowledgeValidationError::WrongCount { .. })
-        ));
+            GuessError::RejectedByStrictMode(KnowledgeValidationError::AbsentLetter {
+                character: 'l'
+            })
+        );
         // failed guesses aren't counted.
         assert_eq!(game.history().len(), 1);
 
@@ -411,4 +569,25 @@ mod tests {
 
         Ok(())
     }
+
+    proptest! {
+        #[test]
+        fn solution_is_consistent_with_its_evaluations(
+            solution in "[a-f]{5}",
+            guesses in prop::collection::vec("[a-f]{5}", 1..=6),
+        ) {
+            let mut knowledge = Knowledge::new(solution.len());
+            for word in guesses {
+                let word_list = HashSet::from_iter(std::iter::once(word.clone()));
+                let evaluation = evaluate(&word, &solution, &word_list).unwrap();
+                knowledge.learn(&Guess {
+                    word,
+                    evaluation,
+                    warning: None,
+                });
+                prop_assert_eq!(knowledge.validate(&solution), Ok(()));
+                prop_assert_eq!(knowledge.validate_nyt_hard(&solution), Ok(()));
+            }
+        }
+    }
 }
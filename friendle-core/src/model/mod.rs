@@ -1,6 +1,8 @@
 pub mod coding;
+pub mod difficulty;
 pub mod evaluation;
 pub mod game;
 pub mod guess_error;
 pub mod knowledge;
+pub mod suggestions;
 pub mod validate_word;
@@ -0,0 +1,189 @@
+use crate::keyboard::KeyboardLayout;
+use std::collections::HashSet;
+
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Suggestions further away than this aren't shown; they're unlikely to be what the player meant.
+const MAX_DISTANCE: f64 = 2.0;
+
+/// Typing a neighbouring key is a more likely typo than an arbitrary other letter.
+const ADJACENT_KEY_SUBSTITUTION_COST: f64 = 0.5;
+
+/// Row and horizontal position (in quarter keys) of the center of a letter key.
+fn key_position(layout: KeyboardLayout, c: char) -> Option<(usize, usize)> {
+    let offsets = layout.row_offsets()?;
+    layout
+        .rows()
+        .iter()
+        .enumerate()
+        .find_map(|(row, keys)| keys.find(c).map(|column| (row, offsets[row] + 4 * column)))
+}
+
+/// Whether two keys touch on a (staggered) physical keyboard.
+/// Layouts without a physical keyboard have no adjacent keys.
+pub fn are_adjacent_keys(layout: KeyboardLayout, a: char, b: char) -> bool {
+    match (key_position(layout, a), key_position(layout, b)) {
+        (Some((row_a, x_a)), Some((row_b, x_b))) => match row_a.abs_diff(row_b) {
+            0 => x_a.abs_diff(x_b) == 4,
+            // Keys in neighbouring rows touch if they are less than a key apart.
+            1 => x_a.abs_diff(x_b) < 4,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn substitution_cost(layout: KeyboardLayout, a: char, b: char) -> f64 {
+    if a == b {
+        0.0
+    } else if are_adjacent_keys(layout, a, b) {
+        ADJACENT_KEY_SUBSTITUTION_COST
+    } else {
+        1.0
+    }
+}
+
+/// Edit distance allowing insertions, deletions, keyboard-weighted substitutions and
+/// transpositions of neighbouring letters.
+pub fn typo_distance(layout: KeyboardLayout, lhs: &str, rhs: &str) -> f64 {
+    let lhs = lhs.chars().collect::<Vec<_>>();
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut distances = vec![vec![0.0; rhs.len() + 1]; lhs.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i as f64;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j as f64;
+    }
+
+    for i in 1..=lhs.len() {
+        for j in 1..=rhs.len() {
+            let mut distance = f64::min(distances[i - 1][j] + 1.0, distances[i][j - 1] + 1.0)
+                .min(distances[i - 1][j - 1] + substitution_cost(layout, lhs[i - 1], rhs[j - 1]));
+            if i > 1 && j > 1 && lhs[i - 1] == rhs[j - 2] && lhs[i - 2] == rhs[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1.0);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[lhs.len()][rhs.len()]
+}
+
+/// Finds up to `MAX_SUGGESTIONS` words from the word list that are close to `word`.
+/// Only words for which `is_allowed` returns true are suggested.
+pub fn suggest_words(
+    word: &str,
+    word_list: &HashSet<String>,
+    layout: KeyboardLayout,
+    is_allowed: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut candidates = word_list
+        .iter()
+        .filter(|candidate| candidate.as_str() != word)
+        .map(|candidate| (typo_distance(layout, word, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+        .filter(|(_, candidate)| is_allowed(candidate))
+        .collect::<Vec<_>>();
+    candidates.sort_by(|(lhs_distance, lhs), (rhs_distance, rhs)| {
+        lhs_distance.total_cmp(rhs_distance).then(lhs.cmp(rhs))
+    });
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{evaluation::Evaluation, game::Guess, knowledge::Knowledge};
+
+    fn word_list(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|word| String::from(*word)).collect()
+    }
+
+    #[test]
+    fn test_adjacent_keys() {
+        assert!(are_adjacent_keys(KeyboardLayout::Qwerty, 'q', 'w'));
+        assert!(are_adjacent_keys(KeyboardLayout::Qwerty, 'w', 's'));
+        assert!(are_adjacent_keys(KeyboardLayout::Qwerty, 's', 'w'));
+        assert!(are_adjacent_keys(KeyboardLayout::Qwerty, 's', 'e'));
+        assert!(are_adjacent_keys(KeyboardLayout::Qwerty, 'p', 'l'));
+        assert!(!are_adjacent_keys(KeyboardLayout::Qwerty, 'q', 'e'));
+        assert!(!are_adjacent_keys(KeyboardLayout::Qwerty, 'q', 'z'));
+        // on AZERTY, `a` and `z` are neighbours
+        assert!(are_adjacent_keys(KeyboardLayout::Azerty, 'a', 'z'));
+        // alphabetical keyboards are only on screen, so there are no likely typos
+        assert!(!are_adjacent_keys(KeyboardLayout::Alphabetical, 'a', 'b'));
+    }
+
+    #[test]
+    fn test_adjacent_keys_on_dvorak() {
+        // `p` is on the key of QWERTY's `r`, above `e` and `u`, not above `a` like `q` on QWERTY.
+        assert!(are_adjacent_keys(KeyboardLayout::Dvorak, 'p', 'e'));
+        assert!(are_adjacent_keys(KeyboardLayout::Dvorak, 'p', 'u'));
+        assert!(!are_adjacent_keys(KeyboardLayout::Dvorak, 'p', 'a'));
+        assert!(!are_adjacent_keys(KeyboardLayout::Dvorak, 'p', 'o'));
+        // `q` is below `o` and `e`, not below `a`.
+        assert!(are_adjacent_keys(KeyboardLayout::Dvorak, 'q', 'o'));
+        assert!(are_adjacent_keys(KeyboardLayout::Dvorak, 'q', 'e'));
+        assert!(!are_adjacent_keys(KeyboardLayout::Dvorak, 'q', 'a'));
+        assert_eq!(typo_distance(KeyboardLayout::Dvorak, "tales", "tales"), 0.0);
+        // `n` is next to `s` on Dvorak, but not on QWERTY
+        assert_eq!(typo_distance(KeyboardLayout::Dvorak, "talen", "tales"), 0.5);
+        assert_eq!(typo_distance(KeyboardLayout::Qwerty, "talen", "tales"), 1.0);
+    }
+
+    #[test]
+    fn test_typo_distance() {
+        assert_eq!(typo_distance(KeyboardLayout::Qwerty, "hello", "hello"), 0.0);
+        // p is next to o on the keyboard
+        assert_eq!(typo_distance(KeyboardLayout::Qwerty, "hellp", "hello"), 0.5);
+        assert_eq!(typo_distance(KeyboardLayout::Qwerty, "hellx", "hello"), 1.0);
+        // transposition
+        assert_eq!(typo_distance(KeyboardLayout::Qwerty, "tabel", "table"), 1.0);
+        // deletion
+        assert_eq!(
+            typo_distance(KeyboardLayout::Qwerty, "tabels", "tales"),
+            2.0
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let words = word_list(&["hello", "jello", "cello", "table", "tales", "zebra"]);
+        assert_eq!(
+            suggest_words("hellp", &words, KeyboardLayout::Qwerty, |_| true),
+            vec!["hello", "jello", "cello"]
+        );
+        assert_eq!(
+            suggest_words("tabel", &words, KeyboardLayout::Qwerty, |_| true),
+            vec!["table", "tales"]
+        );
+        assert!(suggest_words("qqqqq", &words, KeyboardLayout::Qwerty, |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_respect_knowledge() {
+        let words = word_list(&["hello", "jello", "cello"]);
+        let mut knowledge = Knowledge::new(5);
+        knowledge.learn(&Guess {
+            word: String::from("jello"),
+            evaluation: vec![
+                Evaluation::Absent,
+                Evaluation::Correct,
+                Evaluation::Correct,
+                Evaluation::Correct,
+                Evaluation::Correct,
+            ],
+            warning: None,
+        });
+        assert_eq!(
+            suggest_words("hellp", &words, KeyboardLayout::Qwerty, |word| knowledge
+                .validate(word)
+                .is_ok()),
+            vec!["hello", "cello"]
+        );
+    }
+}
@@ -0,0 +1,16 @@
+/// Text output of the game's display functions.
+/// Frontends implement it for their message types, e.g. a Discord message builder.
+pub trait Renderer {
+    fn push(&mut self, text: &str);
+
+    fn push_line(&mut self, text: &str) {
+        self.push(text);
+        self.push("\n");
+    }
+}
+
+impl Renderer for String {
+    fn push(&mut self, text: &str) {
+        self.push_str(text);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::model::guess_error::GuessError;
+use crate::model::validate_word::validate_word_format;
+use std::collections::HashSet;
+
+/// Environment variable frontends read the path of the word list from.
+pub const WORD_LIST_PATH_ENV_VAR: &str = "WORD_LIST_PATH";
+
+pub const DEFAULT_WORD_LIST_PATH: &str = "resources/wordlist.txt";
+
+/// Parses a word list with one word per line.
+/// Lines that aren't valid words are skipped and returned alongside the words.
+pub fn parse_word_list(text: &str) -> (HashSet<String>, Vec<GuessError>) {
+    let mut words = HashSet::new();
+    let mut errors = vec![];
+    for word in text.lines() {
+        match validate_word_format(word) {
+            Ok(_) => {
+                words.insert(String::from(word));
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+    (words, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_word_list() {
+        let (words, errors) = parse_word_list("tales\nhello\ntoolong\nh3llo\n");
+        assert_eq!(
+            words,
+            HashSet::from_iter(["tales", "hello"].map(String::from))
+        );
+        assert_eq!(errors.len(), 2);
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use friendle::buttons::FriendleButton;
+use libfuzzer_sys::fuzz_target;
+
+// Button ids come back from Discord with every click; parsing must never panic,
+// and only ids of our own buttons may be accepted.
+fuzz_target!(|id: &str| {
+    if let Ok(button) = id.parse::<FriendleButton>() {
+        assert_eq!(button.id(), id);
+    }
+});
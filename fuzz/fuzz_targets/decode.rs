@@ -0,0 +1,14 @@
+#![no_main]
+
+use friendle_core::model::coding::{decode, encode, Code};
+use libfuzzer_sys::fuzz_target;
+
+// Codes come straight from users, so decoding must never panic,
+// and every code that decodes has to belong to the decoded word.
+fuzz_target!(|value: u32| {
+    let code = Code { value };
+    if let Some(word) = decode(code) {
+        assert!(word.chars().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(encode(&word), code);
+    }
+});
@@ -0,0 +1,684 @@
+use axum::extract::{FromRequestParts, Path, State};
+use axum::http::request::Parts;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{async_trait, Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+
+use crate::commands::play::{construct_game_opt_result, GameCreationState};
+use crate::constants::MAX_GUESSES;
+use crate::game_store::GameStore;
+use crate::metrics::{GatewayStatus, Metrics};
+use crate::model::coding::{decode, Code};
+use crate::model::game::{Game, GameState, Guess};
+use crate::model::guess_error::{GuessError, InvalidWordError};
+use crate::model::knowledge::{CharacterBound, Knowledge, PositionalKnowledgeState};
+use crate::preferences::PreferenceStore;
+use crate::shutdown::Shutdown;
+use crate::stats::CodeStatsStore;
+use crate::wordlist::WordList;
+
+pub const HTTP_HOST_ENV_VAR: &str = "HTTP_HOST";
+pub const HTTP_PORT_ENV_VAR: &str = "HTTP_PORT";
+pub const API_TOKENS_ENV_VAR: &str = "FRIENDLE_API_TOKENS";
+/// Only reachable from the same machine unless configured otherwise.
+pub const DEFAULT_HTTP_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+pub const DEFAULT_HTTP_PORT: u16 = 8080;
+pub const MIN_API_TOKEN_LENGTH: usize = 16;
+
+/// The stores shared with the Discord frontend.
+#[derive(Clone)]
+pub struct ApiState {
+    pub games: Arc<GameStore>,
+    pub stats: Arc<Mutex<CodeStatsStore>>,
+    pub preferences: Arc<Mutex<PreferenceStore>>,
+    pub word_list: Arc<WordList>,
+    pub metrics: Arc<Metrics>,
+    pub gateway_status: Arc<GatewayStatus>,
+    pub shutdown: Arc<Shutdown>,
+    /// Discord user id per API token.
+    pub api_tokens: Arc<HashMap<String, u64>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    error: String,
+    /// Words the player may have meant, for guesses that aren't in the word list.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suggestions: Vec<String>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, error: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: error.into(),
+            suggestions: vec![],
+        }
+    }
+
+    fn no_game() -> Self {
+        Self::new(StatusCode::NOT_FOUND, "No game in progress.")
+    }
+
+    fn shutting_down() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Friendle is shutting down, try again shortly.",
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self)).into_response()
+    }
+}
+
+/// The Discord user id the request plays as, looked up from its `Authorization: Bearer <token>` header.
+struct Player(u64);
+
+#[async_trait]
+impl FromRequestParts<ApiState> for Player {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &ApiState) -> Result<Self, ApiError> {
+        parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .and_then(|token| state.api_tokens.get(token))
+            .map(|player_id| Player(*player_id))
+            .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Missing or unknown API token."))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateGameRequest {
+    code: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GuessRequest {
+    word: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GuessView {
+    word: String,
+    /// "absent", "present" or "correct" per letter.
+    evaluation: Vec<&'static str>,
+    wasted: bool,
+}
+
+impl From<&Guess> for GuessView {
+    fn from(guess: &Guess) -> Self {
+        Self {
+            word: guess.word.clone(),
+            evaluation: guess
+                .evaluation
+                .iter()
+                .map(|evaluation| evaluation.description())
+                .collect(),
+            wasted: guess.warning.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct GameView {
+    code: u32,
+    state: &'static str,
+    strict_mode: &'static str,
+    max_guesses: usize,
+    guesses: Vec<GuessView>,
+    /// Only revealed once the game is finished.
+    solution: Option<String>,
+}
+
+impl From<&Game> for GameView {
+    fn from(game: &Game) -> Self {
+        let state = game.state();
+        Self {
+            code: game.code().value,
+            state: match state {
+                GameState::InProgress => "in_progress",
+                GameState::Won => "won",
+                GameState::Lost => "lost",
+            },
+            strict_mode: game.get_strict_mode().name(),
+            max_guesses: MAX_GUESSES,
+            guesses: game.history().iter().map(GuessView::from).collect(),
+            solution: (state != GameState::InProgress).then(|| game.solution().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LetterBoundView {
+    letter: char,
+    count: usize,
+    /// Whether `count` is exact or a lower bound.
+    exact: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct KnowledgeView {
+    /// The letter known to be at each position, if any.
+    positions: Vec<Option<char>>,
+    /// Letters known not to be at each position.
+    excluded: Vec<Vec<char>>,
+    required_letters: Vec<LetterBoundView>,
+    absent_letters: Vec<char>,
+}
+
+impl From<&Knowledge> for KnowledgeView {
+    fn from(knowledge: &Knowledge) -> Self {
+        let positions = knowledge.positions();
+        Self {
+            positions: positions
+                .iter()
+                .map(|position| match position.state() {
+                    PositionalKnowledgeState::FixedLetter(c) => Some(*c),
+                    PositionalKnowledgeState::IncorrectLetters(_) => None,
+                })
+                .collect(),
+            excluded: positions
+                .iter()
+                .map(|position| match position.state() {
+                    PositionalKnowledgeState::FixedLetter(_) => vec![],
+                    PositionalKnowledgeState::IncorrectLetters(letters) => {
+                        let mut letters = letters.iter().copied().collect::<Vec<_>>();
+                        letters.sort_unstable();
+                        letters
+                    }
+                })
+                .collect(),
+            required_letters: knowledge
+                .required_letters()
+                .into_iter()
+                .map(|(letter, bound)| LetterBoundView {
+                    letter,
+                    count: bound.count(),
+                    exact: matches!(bound, CharacterBound::Exact(_)),
+                })
+                .collect(),
+            absent_letters: knowledge.absent_letters(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct KeyView {
+    letter: char,
+    /// "unknown", "absent", "present" or "correct".
+    state: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthView {
+    gateway_connected: bool,
+    last_event: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsView {
+    code: u32,
+    difficulty: String,
+    games_started: u32,
+    games_won: u32,
+    games_lost: u32,
+    guess_distribution: [u32; MAX_GUESSES],
+}
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/games", post(create_game))
+        .route("/games/current", get(get_game))
+        .route("/games/current/guesses", post(guess))
+        .route("/games/current/knowledge", get(get_knowledge))
+        .route("/games/current/keyboard", get(get_keyboard))
+        .route("/stats/:code", get(get_stats))
+        .route("/healthz", get(get_health))
+        .route("/metrics", get(get_metrics))
+        .with_state(state)
+}
+
+/// Serves the API on the given address until the server fails or shutdown begins.
+pub async fn serve(host: IpAddr, port: u16, state: ApiState) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind((host, port)).await?;
+    let shutdown = state.shutdown.clone();
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async move { shutdown.requested().await })
+        .await
+}
+
+fn decode_code(code: u32) -> Result<String, ApiError> {
+    decode(Code { value: code })
+        .ok_or_else(|| ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid code: {code}")))
+}
+
+/// Starts a game for the player, following the same rules as `.play`.
+async fn create_game(
+    State(state): State<ApiState>,
+    Player(player_id): Player,
+    Json(request): Json<CreateGameRequest>,
+) -> Result<(StatusCode, Json<GameView>), ApiError> {
+    let _guard = state
+        .shutdown
+        .start_handler()
+        .ok_or_else(ApiError::shutting_down)?;
+    let solution = decode_code(request.code)?;
+    let code = Code {
+        value: request.code,
+    };
+    let difficulty = state.word_list.difficulty(&solution);
+    let default_strict_mode = state
+        .preferences
+        .lock()
+        .unwrap()
+        .get(player_id)
+        .default_strict_mode;
+    match construct_game_opt_result(
+        &state.games,
+        player_id,
+        code,
+        solution,
+        &state.word_list.words,
+        default_strict_mode,
+    )
+    .await
+    {
+        GameCreationState::AlreadyInProgress => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "Game already in progress!",
+        )),
+        GameCreationState::ErrorDuringCreation => Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid code: {}", code.value),
+        )),
+        GameCreationState::SuccessfullyCreated { strict_mode, .. } => {
+            state.metrics.game_started(strict_mode);
+            state
+                .stats
+                .lock()
+                .unwrap()
+                .get_or_insert(code.value, || difficulty)
+                .games_started += 1;
+            let game = state
+                .games
+                .get(player_id)
+                .await
+                .ok_or_else(ApiError::no_game)?;
+            Ok((StatusCode::CREATED, Json(GameView::from(&game))))
+        }
+    }
+}
+
+async fn with_game<T>(
+    state: &ApiState,
+    player_id: u64,
+    view: impl FnOnce(&Game) -> T,
+) -> Result<Json<T>, ApiError> {
+    let game = state
+        .games
+        .get(player_id)
+        .await
+        .ok_or_else(ApiError::no_game)?;
+    Ok(Json(view(&game)))
+}
+
+async fn get_game(
+    State(state): State<ApiState>,
+    Player(player_id): Player,
+) -> Result<Json<GameView>, ApiError> {
+    with_game(&state, player_id, |game| GameView::from(game)).await
+}
+
+async fn get_knowledge(
+    State(state): State<ApiState>,
+    Player(player_id): Player,
+) -> Result<Json<KnowledgeView>, ApiError> {
+    with_game(&state, player_id, |game| {
+        KnowledgeView::from(game.knowledge())
+    })
+    .await
+}
+
+/// The keyboard rows in the player's preferred layout, with the known state of every letter.
+async fn get_keyboard(
+    State(state): State<ApiState>,
+    Player(player_id): Player,
+) -> Result<Json<Vec<Vec<KeyView>>>, ApiError> {
+    let layout = state
+        .preferences
+        .lock()
+        .unwrap()
+        .get(player_id)
+        .keyboard_layout;
+    with_game(&state, player_id, |game| {
+        layout
+            .rows()
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|letter| KeyView {
+                        letter,
+                        state: match game.knowledge().letter_state(letter).to_evaluation() {
+                            Some(evaluation) => evaluation.description(),
+                            None => "unknown",
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    })
+    .await
+}
+
+/// Submits a guess, following the same rules as guesses sent to the bot.
+async fn guess(
+    State(state): State<ApiState>,
+    Player(player_id): Player,
+    Json(request): Json<GuessRequest>,
+) -> Result<Json<GameView>, ApiError> {
+    let _guard = state
+        .shutdown
+        .start_handler()
+        .ok_or_else(ApiError::shutting_down)?;
+    let word = request.word.to_ascii_lowercase();
+    let mut game = state
+        .games
+        .lock(player_id)
+        .await
+        .ok_or_else(ApiError::no_game)?;
+
+    if let Err(err) = game.guess(word.clone(), &state.word_list.words) {
+        state.metrics.guess_rejected(&err);
+        let mut api_error = ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, err.to_string());
+        match err {
+            GuessError::GameNotInProgress => api_error.status = StatusCode::CONFLICT,
+            GuessError::InvalidWord(
+                InvalidWordError::NotInWordList { .. } | InvalidWordError::WrongLength { .. },
+            ) => {
+                let layout = state
+                    .preferences
+                    .lock()
+                    .unwrap()
+                    .get(player_id)
+                    .keyboard_layout;
+                api_error.suggestions = game.suggestions(&word, &state.word_list.words, layout);
+            }
+            _ => {}
+        }
+        return Err(api_error);
+    }
+
+    if game.state() != GameState::InProgress {
+        state.stats.lock().unwrap().record_result(
+            game.code().value,
+            game.state(),
+            game.history().len(),
+        );
+        state
+            .metrics
+            .game_finished(game.state(), game.get_strict_mode());
+    }
+    Ok(Json(GameView::from(&*game)))
+}
+
+async fn get_stats(
+    State(state): State<ApiState>,
+    _: Player,
+    Path(code): Path<u32>,
+) -> Result<Json<StatsView>, ApiError> {
+    let solution = decode_code(code)?;
+    let stats = state
+        .stats
+        .lock()
+        .unwrap()
+        .get_or_default(code, || state.word_list.difficulty(&solution));
+    Ok(Json(StatsView {
+        code,
+        difficulty: stats.difficulty.to_string(),
+        games_started: stats.games_started,
+        games_won: stats.games_won,
+        games_lost: stats.games_lost,
+        guess_distribution: stats.guess_distribution,
+    }))
+}
+
+/// Reports whether the Discord gateway is connected, with 503 if it isn't.
+async fn get_health(State(state): State<ApiState>) -> (StatusCode, Json<HealthView>) {
+    let gateway_connected = state.gateway_status.is_connected();
+    let status = if gateway_connected {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let health = HealthView {
+        gateway_connected,
+        last_event: state
+            .gateway_status
+            .last_event()
+            .map(|time| time.to_rfc3339()),
+    };
+    (status, Json(health))
+}
+
+async fn get_metrics(State(state): State<ApiState>, _: Player) -> impl IntoResponse {
+    let active_games = state.games.count_in_progress();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(active_games),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::coding::encode;
+    use axum::body::Body;
+    use axum::http::Request;
+    use serde_json::{json, Value};
+    use std::collections::HashSet;
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "player-one-0123456789";
+
+    fn test_state() -> ApiState {
+        ApiState {
+            games: Arc::default(),
+            stats: Arc::default(),
+            preferences: Arc::default(),
+            metrics: Arc::default(),
+            gateway_status: Arc::default(),
+            shutdown: Arc::default(),
+            api_tokens: Arc::new(HashMap::from([(String::from(TOKEN), 1)])),
+            word_list: Arc::new(WordList::new(HashSet::from_iter(
+                ["tales", "lanes", "gales"].map(String::from),
+            ))),
+        }
+    }
+
+    async fn request(
+        state: &ApiState,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        request_with_token(state, Some(TOKEN), method, uri, body).await
+    }
+
+    async fn request_with_token(
+        state: &ApiState,
+        token: Option<&str>,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {token}"));
+        }
+        let request = match body {
+            Some(body) => request.body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+        let response = router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_play_game() {
+        let state = test_state();
+        let code = encode("tales").value;
+
+        let (status, game) = request(&state, "POST", "/games", Some(json!({"code": code}))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(game["state"], "in_progress");
+        assert_eq!(game["solution"], Value::Null);
+
+        let (status, _) = request(&state, "POST", "/games", Some(json!({"code": code}))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, error) = request(
+            &state,
+            "POST",
+            "/games/current/guesses",
+            Some(json!({"word": "talse"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error["suggestions"][0], "tales");
+
+        let (status, game) = request(
+            &state,
+            "POST",
+            "/games/current/guesses",
+            Some(json!({"word": "LANES"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            game["guesses"][0]["evaluation"],
+            json!(["present", "correct", "absent", "correct", "correct"])
+        );
+
+        let (_, knowledge) = request(&state, "GET", "/games/current/knowledge", None).await;
+        assert_eq!(knowledge["positions"], json!([null, "a", null, "e", "s"]));
+        assert_eq!(knowledge["excluded"][0], json!(["l"]));
+        assert_eq!(knowledge["absent_letters"], json!(["n"]));
+
+        let (_, keyboard) = request(&state, "GET", "/games/current/keyboard", None).await;
+        assert_eq!(keyboard[0][0], json!({"letter": "q", "state": "unknown"}));
+        assert_eq!(keyboard[1][8], json!({"letter": "l", "state": "present"}));
+
+        let (_, game) = request(
+            &state,
+            "POST",
+            "/games/current/guesses",
+            Some(json!({"word": "tales"})),
+        )
+        .await;
+        assert_eq!(game["state"], "won");
+        assert_eq!(game["solution"], "tales");
+
+        let metrics = state.metrics.render(0);
+        assert!(metrics.contains("friendle_games_started_total{mode=\"off\"} 1"));
+        assert!(metrics.contains("friendle_games_finished_total{outcome=\"won\",mode=\"off\"} 1"));
+        assert!(metrics.contains("reason=\"NotInWordList\"} 1"));
+
+        let (_, stats) = request(&state, "GET", &format!("/stats/{code}"), None).await;
+        assert_eq!(stats["games_started"], 1);
+        assert_eq!(stats["games_won"], 1);
+        assert_eq!(stats["guess_distribution"], json!([0, 1, 0, 0, 0, 0]));
+    }
+
+    #[tokio::test]
+    async fn test_stats_lookup_does_not_store_codes() {
+        let state = test_state();
+        let code = encode("gales").value;
+        let (status, stats) = request(&state, "GET", &format!("/stats/{code}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(stats["games_started"], 0);
+        assert!(state.stats.lock().unwrap().stats_per_code.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_game() {
+        let state = test_state();
+        let (status, error) = request(&state, "GET", "/games/current", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error["error"], "No game in progress.");
+    }
+
+    #[tokio::test]
+    async fn test_game_routes_require_a_known_token() {
+        let state = test_state();
+        let code = encode("tales").value;
+        for token in [None, Some("unknown-token-0123456789")] {
+            let (status, error) =
+                request_with_token(&state, token, "POST", "/games", Some(json!({"code": code})))
+                    .await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(error["error"], "Missing or unknown API token.");
+        }
+        assert!(state.games.is_empty());
+
+        // Statistics and metrics aren't public either; only the health check is.
+        for uri in [format!("/stats/{code}"), String::from("/metrics")] {
+            let (status, _) = request_with_token(&state, None, "GET", &uri, None).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+
+        // The game is started for the user the token belongs to.
+        request(&state, "POST", "/games", Some(json!({"code": code}))).await;
+        assert!(state.games.get(1).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_guesses_rejected_during_shutdown() {
+        let state = test_state();
+        state.shutdown.request();
+        let (status, _) = request(
+            &state,
+            "POST",
+            "/games/current/guesses",
+            Some(json!({"word": "tales"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(state.shutdown.summary().rejected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let state = test_state();
+        let (status, health) = request_with_token(&state, None, "GET", "/healthz", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(health["last_event"], Value::Null);
+
+        state.gateway_status.set_connected(true);
+        state.gateway_status.record_event();
+        let (status, health) = request(&state, "GET", "/healthz", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(health["gateway_connected"], true);
+        assert!(health["last_event"].is_string());
+    }
+}
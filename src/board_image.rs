@@ -0,0 +1,231 @@
+use crate::constants::{MAX_GUESSES, WORD_LENGTH};
+use crate::model::evaluation::EmojiTheme;
+use crate::model::game::{Game, GameState};
+
+const TILE_SIZE: usize = 64;
+const TILE_GAP: usize = 8;
+const MARGIN: usize = 16;
+const BORDER_WIDTH: usize = 3;
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+const GLYPH_SCALE: usize = 6;
+
+pub const IMAGE_WIDTH: usize = 2 * MARGIN + WORD_LENGTH * TILE_SIZE + (WORD_LENGTH - 1) * TILE_GAP;
+pub const IMAGE_HEIGHT: usize = 2 * MARGIN + MAX_GUESSES * TILE_SIZE + (MAX_GUESSES - 1) * TILE_GAP;
+
+type Rgba = [u8; 4];
+
+// The background is transparent so the image looks fine in both Discord's dark and light mode.
+const BACKGROUND: Rgba = [0, 0, 0, 0];
+const EMPTY_TILE_BORDER: Rgba = [135, 138, 140, 255];
+const WASTED_TILE_BORDER: Rgba = [255, 172, 51, 255];
+const LETTER: Rgba = [255, 255, 255, 255];
+
+/// Uppercase letters of a 5x7 bitmap font; each byte is one row, the lowest 5 bits are the pixels.
+const FONT: [[u8; GLYPH_HEIGHT]; 26] = [
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // C
+    [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E], // D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // Z
+];
+
+fn glyph(letter: char) -> Option<&'static [u8; GLYPH_HEIGHT]> {
+    let letter = letter.to_ascii_uppercase();
+    letter
+        .is_ascii_uppercase()
+        .then(|| &FONT[(letter as u8 - b'A') as usize])
+}
+
+/// RGBA pixel buffer we rasterize the board into.
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(background: Rgba) -> Self {
+        Self {
+            pixels: background.repeat(IMAGE_WIDTH * IMAGE_HEIGHT),
+        }
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgba) {
+        for row in y..(y + height).min(IMAGE_HEIGHT) {
+            for column in x..(x + width).min(IMAGE_WIDTH) {
+                let offset = 4 * (row * IMAGE_WIDTH + column);
+                self.pixels[offset..offset + 4].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn outline_rect(&mut self, x: usize, y: usize, size: usize, color: Rgba) {
+        self.fill_rect(x, y, size, BORDER_WIDTH, color);
+        self.fill_rect(x, y + size - BORDER_WIDTH, size, BORDER_WIDTH, color);
+        self.fill_rect(x, y, BORDER_WIDTH, size, color);
+        self.fill_rect(x + size - BORDER_WIDTH, y, BORDER_WIDTH, size, color);
+    }
+
+    fn draw_letter(&mut self, x: usize, y: usize, letter: char, color: Rgba) {
+        let glyph = match glyph(letter) {
+            Some(glyph) => glyph,
+            None => return,
+        };
+        // center the scaled glyph in the tile
+        let x = x + (TILE_SIZE - GLYPH_WIDTH * GLYPH_SCALE) / 2;
+        let y = y + (TILE_SIZE - GLYPH_HEIGHT * GLYPH_SCALE) / 2;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                    self.fill_rect(
+                        x + column * GLYPH_SCALE,
+                        y + row * GLYPH_SCALE,
+                        GLYPH_SCALE,
+                        GLYPH_SCALE,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    fn encode_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder =
+                png::Encoder::new(&mut bytes, IMAGE_WIDTH as u32, IMAGE_HEIGHT as u32);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+        }
+        Ok(bytes)
+    }
+}
+
+fn tile_position(row: usize, column: usize) -> (usize, usize) {
+    (
+        MARGIN + column * (TILE_SIZE + TILE_GAP),
+        MARGIN + row * (TILE_SIZE + TILE_GAP),
+    )
+}
+
+/// Renders the board of the given game as a PNG image, with the remaining guesses as empty tiles.
+/// Like `Game::display_state`, the guessed letters are only shown while the game is in progress.
+pub fn render_board(game: &Game, theme: &EmojiTheme) -> Result<Vec<u8>, png::EncodingError> {
+    let show_letters = game.state() == GameState::InProgress;
+    let mut canvas = Canvas::new(BACKGROUND);
+    for row in 0..MAX_GUESSES {
+        let guess = game.history().get(row);
+        for column in 0..WORD_LENGTH {
+            let (x, y) = tile_position(row, column);
+            let guess = match guess {
+                Some(guess) => guess,
+                None => {
+                    canvas.outline_rect(x, y, TILE_SIZE, EMPTY_TILE_BORDER);
+                    continue;
+                }
+            };
+            let [r, g, b] = theme.tile_color(guess.evaluation[column]);
+            canvas.fill_rect(x, y, TILE_SIZE, TILE_SIZE, [r, g, b, 255]);
+            if guess.warning.is_some() {
+                // mark wasted guesses
+                canvas.outline_rect(x, y, TILE_SIZE, WASTED_TILE_BORDER);
+            }
+            if show_letters {
+                if let Some(letter) = guess.word.chars().nth(column) {
+                    canvas.draw_letter(x, y, letter, LETTER);
+                }
+            }
+        }
+    }
+    canvas.encode_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::coding::encode;
+    use crate::model::evaluation::Evaluation;
+    use std::collections::HashSet;
+
+    /// Decodes the image and returns the color of a single pixel.
+    fn pixel_at(png_bytes: &[u8], x: usize, y: usize) -> Rgba {
+        let decoder = png::Decoder::new(png_bytes);
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        assert_eq!(
+            (info.width as usize, info.height as usize),
+            (IMAGE_WIDTH, IMAGE_HEIGHT)
+        );
+        let offset = 4 * (y * IMAGE_WIDTH + x);
+        buffer[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_font_covers_alphabet() {
+        for letter in 'a'..='z' {
+            let glyph = glyph(letter).unwrap();
+            assert!(glyph.iter().any(|row| *row != 0), "{letter}");
+            assert!(glyph.iter().all(|row| *row < 1 << GLYPH_WIDTH), "{letter}");
+        }
+        assert!(glyph('1').is_none());
+    }
+
+    #[test]
+    fn test_render_board() {
+        let word_list = HashSet::from_iter(["tales", "lanes"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        game.guess(String::from("lanes"), &word_list).unwrap();
+        let theme = EmojiTheme::Nyt;
+        let png = render_board(&game, &theme).unwrap();
+
+        // "lanes" vs "tales": L present, A correct, N absent.
+        // The tile corners are never covered by letters.
+        let [r, g, b] = theme.tile_color(Evaluation::Present);
+        let (x, y) = tile_position(0, 0);
+        assert_eq!(pixel_at(&png, x, y), [r, g, b, 255]);
+        let [r, g, b] = theme.tile_color(Evaluation::Correct);
+        let (x, y) = tile_position(0, 1);
+        assert_eq!(pixel_at(&png, x + 1, y + 1), [r, g, b, 255]);
+        let [r, g, b] = theme.tile_color(Evaluation::Absent);
+        let (x, y) = tile_position(0, 2);
+        assert_eq!(pixel_at(&png, x, y + TILE_SIZE - 1), [r, g, b, 255]);
+
+        // letters are drawn while the game is in progress; the top stroke of the L is at its left edge.
+        let (x, y) = tile_position(0, 0);
+        let letter_x = x + (TILE_SIZE - GLYPH_WIDTH * GLYPH_SCALE) / 2;
+        let letter_y = y + (TILE_SIZE - GLYPH_HEIGHT * GLYPH_SCALE) / 2;
+        assert_eq!(pixel_at(&png, letter_x, letter_y), LETTER);
+
+        // remaining guesses are empty, outlined tiles
+        let (x, y) = tile_position(1, 0);
+        assert_eq!(pixel_at(&png, x, y), EMPTY_TILE_BORDER);
+        assert_eq!(
+            pixel_at(&png, x + TILE_SIZE / 2, y + TILE_SIZE / 2),
+            BACKGROUND
+        );
+    }
+}
@@ -1,68 +1,295 @@
 use serenity::{
-    builder::CreateButton,
-    client::Context,
-    model::interactions::message_component::{ButtonStyle, MessageComponentInteraction},
+    builder::CreateButton, model::interactions::message_component::ButtonStyle, prelude::TypeMap,
     utils::MessageBuilder,
 };
 
 use crate::{
-    model::{evaluation::EmojiMode, game::GameState},
-    player::PlayerState,
+    game_store::GameStore,
+    i18n::{BotMessage, Locale, Localize},
+    model::{
+        evaluation::{EmojiMode, EmojiTheme},
+        game::{Game, GameState},
+    },
+    preferences::{DisplayMode, PreferenceStore, UserPreferences},
+    render::DiscordRenderer,
+    transport::{ButtonClick, Transport},
     util::adjust_buttons,
 };
 
-pub struct CopyResultButton {}
+/// Formats the result of a finished game can be shared in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ShareFormat {
+    /// Header and emoji rows.
+    Emoji,
+    /// Like `Emoji`, with the guessed word hidden under a spoiler tag next to each row.
+    Spoiler,
+    /// Header and unicode emoji rows in a code block, for pasting outside of Discord.
+    CodeBlock,
+}
 
-impl CopyResultButton {
-    pub const ID: &'static str = "copy_result";
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CopyResultButton {
+    pub format: ShareFormat,
 }
 
+pub const COPY_RESULT_BUTTON_ID: &str = "copy_result";
+
+pub const COPY_RESULT_SPOILER_BUTTON_ID: &str = "copy_result_spoiler";
+
+pub const COPY_RESULT_CODE_BLOCK_BUTTON_ID: &str = "copy_result_text";
+
 impl CopyResultButton {
-    pub fn button() -> CreateButton {
-        let mut show_keyboard_button = CreateButton::default();
-        show_keyboard_button.custom_id(Self::ID);
-        show_keyboard_button.label("Copy Result");
-        show_keyboard_button.style(ButtonStyle::Primary);
-        show_keyboard_button
+    pub const ALL: [CopyResultButton; 3] = [
+        CopyResultButton::new(ShareFormat::Emoji),
+        CopyResultButton::new(ShareFormat::Spoiler),
+        CopyResultButton::new(ShareFormat::CodeBlock),
+    ];
+
+    pub const fn new(format: ShareFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn get_id(self) -> &'static str {
+        match self.format {
+            ShareFormat::Emoji => COPY_RESULT_BUTTON_ID,
+            ShareFormat::Spoiler => COPY_RESULT_SPOILER_BUTTON_ID,
+            ShareFormat::CodeBlock => COPY_RESULT_CODE_BLOCK_BUTTON_ID,
+        }
+    }
+
+    pub fn button(self, locale: Locale) -> CreateButton {
+        let mut copy_result_button = CreateButton::default();
+        copy_result_button.custom_id(self.get_id());
+        copy_result_button.label(BotMessage::CopyResultLabel(self.format).localize(locale));
+        copy_result_button.style(ButtonStyle::Primary);
+        copy_result_button
     }
 
     pub async fn handle_interaction(
-        ctx: &Context,
-        mci: &mut MessageComponentInteraction,
+        self,
+        data: &TypeMap,
+        transport: &dyn Transport,
+        click: &ButtonClick,
     ) -> anyhow::Result<()> {
-        let data = ctx.data.read().await;
-        let player_state = data.get::<PlayerState>().unwrap();
-        let user = &mci.user;
-        let game = {
-            let mut lock = player_state.lock().unwrap();
-            lock.games_per_player.get_mut(&user.id.0).cloned()
+        let game = match data.get::<GameStore>().unwrap().get(click.user_id).await {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let preferences = {
+            let preference_store = data.get::<PreferenceStore>().unwrap();
+            let lock = preference_store.lock().unwrap();
+            lock.get(click.user_id)
         };
 
-        if game.is_none() {
-            return Ok(());
+        let mut msg_builder = MessageBuilder::new();
+        if game.state() == GameState::InProgress {
+            msg_builder.push_line_safe(BotMessage::GameNotFinished.localize(preferences.locale));
+        } else {
+            // Game is finished -> Display state
+            display_result(&game, self.format, &preferences, &mut msg_builder);
         }
-        let game = game.unwrap();
-
-        mci.create_interaction_response(ctx, |r| {
-            r.interaction_response_data(|msg| {
-                let mut msg_builder = MessageBuilder::new();
-                if game.state() == GameState::InProgress {
-                    msg_builder.push_line_safe("The current game isn't finished yet.");
-                } else {
-                    // Game is finished -> Display state
-                    game.display_game_state_header(&mut msg_builder);
-                    // evaluation converted to emojis
-                    game.display_state(&mut msg_builder, EmojiMode::DiscordName);
-                }
-                msg.content(msg_builder.build());
-                msg
-            });
-            r
-        })
-        .await?;
-
-        adjust_buttons(mci, &game, ctx).await?;
+        transport.respond(click, msg_builder.build()).await?;
+
+        adjust_buttons(transport, click, &game, preferences.locale).await?;
 
         Ok(())
     }
 }
+
+fn display_result(
+    game: &Game,
+    format: ShareFormat,
+    preferences: &UserPreferences,
+    message_builder: &mut MessageBuilder,
+) {
+    let locale = preferences.locale;
+    match format {
+        ShareFormat::Emoji => {
+            game.display_game_state_header(&mut DiscordRenderer(message_builder), locale);
+            message_builder.push(result_rows(
+                game,
+                preferences,
+                preferences.result_emoji_mode,
+                &preferences.theme,
+            ));
+        }
+        ShareFormat::Spoiler => {
+            game.display_game_state_header(&mut DiscordRenderer(message_builder), locale);
+            let rows = result_rows(
+                game,
+                preferences,
+                preferences.result_emoji_mode,
+                &preferences.theme,
+            );
+            for (row, guess) in rows.lines().zip(game.history()) {
+                let word = guess.word.to_ascii_uppercase();
+                message_builder.push_line(format!("{row} ||`{word}`||"));
+            }
+        }
+        ShareFormat::CodeBlock => {
+            // Discord emoji names, custom emojis and back ticks don't render in code blocks.
+            let mut header = String::new();
+            game.display_game_state_header(&mut header, locale);
+            let theme = if preferences.theme.uses_custom_emojis() {
+                EmojiTheme::default()
+            } else {
+                preferences.theme.clone()
+            };
+            message_builder.push_line("```");
+            message_builder.push(header.replace('`', ""));
+            message_builder.push(result_rows(game, preferences, EmojiMode::Unicode, &theme));
+            message_builder.push_line("```");
+        }
+    }
+}
+
+/// One line per guess of a finished game: evaluation emojis, or sentences in screen reader mode.
+fn result_rows(
+    game: &Game,
+    preferences: &UserPreferences,
+    emoji_mode: EmojiMode,
+    theme: &EmojiTheme,
+) -> String {
+    let mut rows = String::new();
+    if preferences.display_mode == DisplayMode::ScreenReader {
+        game.display_state_as_text(&mut rows, preferences.locale);
+    } else {
+        game.display_state(&mut rows, emoji_mode, theme);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::coding::encode;
+    use crate::model::game::StrictMode;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_share_formats() {
+        let word_list = HashSet::from_iter(["tales", "lanes"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        game.guess(String::from("lanes"), &word_list).unwrap();
+        game.guess(String::from("tales"), &word_list).unwrap();
+        let code = game.code().value;
+        let preferences = UserPreferences::default();
+
+        let mut message_builder = MessageBuilder::new();
+        display_result(
+            &game,
+            ShareFormat::Spoiler,
+            &preferences,
+            &mut message_builder,
+        );
+        assert_eq!(
+            message_builder.build(),
+            format!(
+                "Friendle `{code}`: 2/6 \n\
+                 :yellow_square::green_square::red_square::green_square::green_square: ||`LANES`||\n\
+                 :green_square::green_square::green_square::green_square::green_square: ||`TALES`||\n"
+            )
+        );
+
+        let mut message_builder = MessageBuilder::new();
+        display_result(
+            &game,
+            ShareFormat::CodeBlock,
+            &preferences,
+            &mut message_builder,
+        );
+        assert_eq!(
+            message_builder.build(),
+            format!("```\nFriendle {code}: 2/6 \n🟨🟩🟥🟩🟩\n🟩🟩🟩🟩🟩\n```\n")
+        );
+    }
+
+    fn shared(game: &Game, format: ShareFormat, preferences: &UserPreferences) -> String {
+        let mut message_builder = MessageBuilder::new();
+        display_result(game, format, preferences, &mut message_builder);
+        message_builder.build()
+    }
+
+    #[test]
+    fn test_share_formats_in_screen_reader_mode() {
+        let word_list = HashSet::from_iter(["tales", "lanes"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        game.guess(String::from("lanes"), &word_list).unwrap();
+        game.guess(String::from("tales"), &word_list).unwrap();
+        let code = game.code().value;
+        let preferences = UserPreferences {
+            display_mode: DisplayMode::ScreenReader,
+            ..UserPreferences::default()
+        };
+
+        assert_eq!(
+            shared(&game, ShareFormat::Spoiler, &preferences),
+            format!(
+                "Friendle `{code}`: 2/6 \n\
+                 Guess 1: present, correct, absent, correct, correct. ||`LANES`||\n\
+                 Guess 2: correct, correct, correct, correct, correct. ||`TALES`||\n"
+            )
+        );
+        assert_eq!(
+            shared(&game, ShareFormat::CodeBlock, &preferences),
+            format!(
+                "```\nFriendle {code}: 2/6 \n\
+                 Guess 1: present, correct, absent, correct, correct.\n\
+                 Guess 2: correct, correct, correct, correct, correct.\n```\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_spoilers_keep_the_wasted_guess_marker() {
+        let word_list = HashSet::from_iter(["tales", "lanes"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        game.set_strict_mode(StrictMode::Warn).unwrap();
+        game.guess(String::from("lanes"), &word_list).unwrap();
+        // `l` is known not to be in the first position
+        game.guess(String::from("lanes"), &word_list).unwrap();
+        game.guess(String::from("tales"), &word_list).unwrap();
+
+        let spoiler = shared(&game, ShareFormat::Spoiler, &UserPreferences::default());
+        let rows = spoiler.lines().collect::<Vec<_>>();
+        assert!(rows[0].ends_with("(1 wasted)"));
+        assert!(!rows[1].contains(":warning:"));
+        assert!(rows[2].ends_with(":warning: ||`LANES`||"));
+    }
+
+    #[test]
+    fn test_code_blocks_fall_back_from_custom_emojis() {
+        let word_list = HashSet::from_iter(["tales"].map(String::from));
+        let solution = String::from("tales");
+        let mut game = Game::new(encode(&solution), solution, &word_list).unwrap();
+        game.guess(String::from("tales"), &word_list).unwrap();
+
+        let guild_emojis = UserPreferences {
+            theme: EmojiTheme::Custom(
+                [
+                    "<:no:123456789012345678>",
+                    "<:maybe:123456789012345678>",
+                    "<:yes:123456789012345678>",
+                ]
+                .map(String::from),
+            ),
+            ..UserPreferences::default()
+        };
+        let code_block = shared(&game, ShareFormat::CodeBlock, &guild_emojis);
+        assert!(code_block.contains("\n🟩🟩🟩🟩🟩\n"));
+        assert!(!code_block.contains("<:"));
+        // custom emojis are kept outside of code blocks
+        assert!(
+            shared(&game, ShareFormat::Emoji, &guild_emojis).contains("<:yes:123456789012345678>")
+        );
+
+        let unicode_emojis = UserPreferences {
+            theme: EmojiTheme::Custom(["❌", "⭕", "✅"].map(String::from)),
+            ..UserPreferences::default()
+        };
+        assert!(shared(&game, ShareFormat::CodeBlock, &unicode_emojis).contains("\n✅✅✅✅✅\n"));
+    }
+}
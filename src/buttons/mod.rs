@@ -1,6 +1,7 @@
 pub mod copy_result_button;
 pub mod mode_button;
 pub mod show_keyboard_button;
+pub mod show_knowledge_button;
 
 use std::{
     error::Error as StdError,
@@ -10,20 +11,27 @@ use std::{
 
 use serenity::{
     builder::CreateButton, client::Context,
-    model::interactions::message_component::MessageComponentInteraction,
+    model::interactions::message_component::MessageComponentInteraction, prelude::TypeMap,
 };
+use tracing::{error, field, info_span, Instrument};
 
-use copy_result_button::CopyResultButton;
+use copy_result_button::{CopyResultButton, ShareFormat};
 use show_keyboard_button::ShowKeyboardButton;
+use show_knowledge_button::ShowKnowledgeButton;
 
+use crate::game_store::GameStore;
+use crate::i18n::Locale;
+use crate::metrics::Metrics;
 use crate::model::game::StrictMode;
+use crate::transport::{ButtonClick, DiscordTransport, Transport};
 use mode_button::ModeButton;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum FriendleButton {
     ShowKeyboard,
+    ShowKnowledge,
     ModeChangeButton(ModeButton),
-    CopyResultButton,
+    CopyResultButton(CopyResultButton),
 }
 
 #[derive(Debug)]
@@ -43,56 +51,126 @@ impl FromStr for FriendleButton {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             ShowKeyboardButton::ID => Ok(FriendleButton::ShowKeyboard),
+            ShowKnowledgeButton::ID => Ok(FriendleButton::ShowKnowledge),
             mode_button::STRICT_MODE_BUTTON_ID => {
                 Ok(FriendleButton::ModeChangeButton(ModeButton {
                     mode: StrictMode::Enabled,
                 }))
             }
+            mode_button::SOFT_STRICT_MODE_BUTTON_ID => {
+                Ok(FriendleButton::ModeChangeButton(ModeButton {
+                    mode: StrictMode::Warn,
+                }))
+            }
+            mode_button::NYT_HARD_MODE_BUTTON_ID => {
+                Ok(FriendleButton::ModeChangeButton(ModeButton {
+                    mode: StrictMode::NytHard,
+                }))
+            }
             mode_button::NON_STRICT_MODE_BUTTON_ID => {
                 Ok(FriendleButton::ModeChangeButton(ModeButton {
                     mode: StrictMode::Disabled,
                 }))
             }
-            CopyResultButton::ID => Ok(FriendleButton::CopyResultButton),
+            copy_result_button::COPY_RESULT_BUTTON_ID => Ok(FriendleButton::CopyResultButton(
+                CopyResultButton::new(ShareFormat::Emoji),
+            )),
+            copy_result_button::COPY_RESULT_SPOILER_BUTTON_ID => Ok(
+                FriendleButton::CopyResultButton(CopyResultButton::new(ShareFormat::Spoiler)),
+            ),
+            copy_result_button::COPY_RESULT_CODE_BLOCK_BUTTON_ID => Ok(
+                FriendleButton::CopyResultButton(CopyResultButton::new(ShareFormat::CodeBlock)),
+            ),
             _ => Err(ButtonParseError(s.to_string())),
         }
     }
 }
 
 impl FriendleButton {
-    pub async fn handle_interaction(self, ctx: &Context, mci: &mut MessageComponentInteraction) {
-        if let Err(e) = match self {
-            FriendleButton::ShowKeyboard => ShowKeyboardButton::handle_interaction(ctx, mci).await,
-            FriendleButton::ModeChangeButton(mode) => mode.handle_interaction(ctx, mci).await,
-            FriendleButton::CopyResultButton => {
-                CopyResultButton::handle_interaction(ctx, mci).await
+    pub async fn handle_interaction(self, ctx: &Context, mci: &MessageComponentInteraction) {
+        let data = ctx.data.read().await;
+        self.handle_click(&data, &DiscordTransport::from(ctx), &ButtonClick::from(mci))
+            .await;
+    }
+
+    pub async fn handle_click(
+        self,
+        data: &TypeMap,
+        transport: &dyn Transport,
+        click: &ButtonClick,
+    ) {
+        let span = info_span!(
+            "button_interaction",
+            user_id = click.user_id,
+            button = self.id(),
+            code = field::Empty,
+            game_state = field::Empty,
+        );
+        data.get::<Metrics>()
+            .unwrap()
+            .button_interaction(self.name());
+        if let Some(game) = data.get::<GameStore>().unwrap().get(click.user_id).await {
+            span.record("code", game.code().value);
+            span.record("game_state", field::debug(game.state()));
+        }
+        if let Err(e) = async {
+            match self {
+                FriendleButton::ShowKeyboard => {
+                    ShowKeyboardButton::handle_interaction(data, transport, click).await
+                }
+                FriendleButton::ShowKnowledge => {
+                    ShowKnowledgeButton::handle_interaction(data, transport, click).await
+                }
+                FriendleButton::ModeChangeButton(mode) => {
+                    mode.handle_interaction(data, transport, click).await
+                }
+                FriendleButton::CopyResultButton(button) => {
+                    button.handle_interaction(data, transport, click).await
+                }
             }
-        } {
-            eprintln!("Error during button interaction: {e}");
-            eprintln!("mci: {mci:?}");
+        }
+        .instrument(span.clone())
+        .await
+        {
+            span.in_scope(|| error!("Error during button interaction: {e}"));
         }
     }
 
-    pub fn create_button(&self) -> CreateButton {
+    pub fn create_button(&self, locale: Locale) -> CreateButton {
         match self {
-            FriendleButton::ShowKeyboard => ShowKeyboardButton::button(),
-            FriendleButton::ModeChangeButton(button) => button.mode_button(),
-            FriendleButton::CopyResultButton => CopyResultButton::button(),
+            FriendleButton::ShowKeyboard => ShowKeyboardButton::button(locale),
+            FriendleButton::ShowKnowledge => ShowKnowledgeButton::button(locale),
+            FriendleButton::ModeChangeButton(button) => button.mode_button(locale),
+            FriendleButton::CopyResultButton(button) => button.button(locale),
+        }
+    }
+
+    /// Name of the variant, used as metrics label.
+    pub fn name(self) -> &'static str {
+        match self {
+            FriendleButton::ShowKeyboard => "ShowKeyboard",
+            FriendleButton::ShowKnowledge => "ShowKnowledge",
+            FriendleButton::ModeChangeButton(_) => "ModeChangeButton",
+            FriendleButton::CopyResultButton(_) => "CopyResultButton",
         }
     }
 
     pub fn id(self) -> &'static str {
         match self {
             FriendleButton::ShowKeyboard => ShowKeyboardButton::ID,
+            FriendleButton::ShowKnowledge => ShowKnowledgeButton::ID,
             FriendleButton::ModeChangeButton(button) => button.get_id(),
-            FriendleButton::CopyResultButton => CopyResultButton::ID,
+            FriendleButton::CopyResultButton(button) => button.get_id(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::buttons::mode_button::{NON_STRICT_MODE_BUTTON_ID, STRICT_MODE_BUTTON_ID};
+    use crate::buttons::mode_button::{
+        NON_STRICT_MODE_BUTTON_ID, NYT_HARD_MODE_BUTTON_ID, SOFT_STRICT_MODE_BUTTON_ID,
+        STRICT_MODE_BUTTON_ID,
+    };
 
     use super::*;
 
@@ -103,15 +181,33 @@ mod tests {
             Ok(FriendleButton::ShowKeyboard)
         ));
         assert!(matches!(
-            FriendleButton::from_str(CopyResultButton::ID),
-            Ok(FriendleButton::CopyResultButton)
+            FriendleButton::from_str(ShowKnowledgeButton::ID),
+            Ok(FriendleButton::ShowKnowledge)
         ));
+        for button in CopyResultButton::ALL {
+            assert_eq!(
+                FriendleButton::from_str(button.get_id()).unwrap(),
+                FriendleButton::CopyResultButton(button)
+            );
+        }
         assert!(matches!(
             FriendleButton::from_str(NON_STRICT_MODE_BUTTON_ID),
             Ok(FriendleButton::ModeChangeButton(ModeButton {
                 mode: StrictMode::Disabled
             }))
         ));
+        assert!(matches!(
+            FriendleButton::from_str(SOFT_STRICT_MODE_BUTTON_ID),
+            Ok(FriendleButton::ModeChangeButton(ModeButton {
+                mode: StrictMode::Warn
+            }))
+        ));
+        assert!(matches!(
+            FriendleButton::from_str(NYT_HARD_MODE_BUTTON_ID),
+            Ok(FriendleButton::ModeChangeButton(ModeButton {
+                mode: StrictMode::NytHard
+            }))
+        ));
         assert!(matches!(
             FriendleButton::from_str(STRICT_MODE_BUTTON_ID),
             Ok(FriendleButton::ModeChangeButton(ModeButton {
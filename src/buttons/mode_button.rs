@@ -1,16 +1,16 @@
 use serenity::{
     builder::CreateButton,
-    client::Context,
-    model::{
-        channel::ReactionType,
-        interactions::message_component::{ButtonStyle, MessageComponentInteraction},
-    },
+    model::{channel::ReactionType, interactions::message_component::ButtonStyle},
+    prelude::TypeMap,
     utils::MessageBuilder,
 };
 
 use crate::{
-    model::game::{ModeChangeError, StrictMode},
-    player::PlayerState,
+    game_store::GameStore,
+    i18n::{BotMessage, Locale, Localize},
+    model::game::StrictMode,
+    preferences::PreferenceStore,
+    transport::{ButtonClick, Transport},
     util::adjust_buttons,
 };
 
@@ -21,12 +21,18 @@ pub struct ModeButton {
 
 pub const STRICT_MODE_BUTTON_ID: &str = "strict";
 
+pub const SOFT_STRICT_MODE_BUTTON_ID: &str = "softstrict";
+
+pub const NYT_HARD_MODE_BUTTON_ID: &str = "nythard";
+
 pub const NON_STRICT_MODE_BUTTON_ID: &str = "nonstrict";
 
 impl ModeButton {
     pub fn get_id(self) -> &'static str {
         match self.mode {
             StrictMode::Disabled => NON_STRICT_MODE_BUTTON_ID,
+            StrictMode::Warn => SOFT_STRICT_MODE_BUTTON_ID,
+            StrictMode::NytHard => NYT_HARD_MODE_BUTTON_ID,
             StrictMode::Enabled => STRICT_MODE_BUTTON_ID,
         }
     }
@@ -37,16 +43,14 @@ impl ModeButton {
 }
 
 impl ModeButton {
-    pub fn mode_button(self) -> CreateButton {
+    pub fn mode_button(self, locale: Locale) -> CreateButton {
         let mut mode_button = CreateButton::default();
         mode_button.custom_id(self.get_id());
-        let mode_text = match self.mode {
-            StrictMode::Enabled => "Enable strict mode",
-            StrictMode::Disabled => "Disable strict mode",
-        };
-        mode_button.label(mode_text);
+        mode_button.label(BotMessage::ModeButtonLabel(self.mode).localize(locale));
         mode_button.emoji(ReactionType::Unicode(String::from(match self.mode {
             StrictMode::Enabled => "🧐",  // display strict mode with monocle face
+            StrictMode::Warn => "⚠️",     // soft strict mode: warning sign
+            StrictMode::NytHard => "🗞️",  // NYT hard mode: newspaper
             StrictMode::Disabled => "🙈", // non-strict mode: see-no-evil monkey
         })));
         mode_button.style(ButtonStyle::Primary);
@@ -55,49 +59,30 @@ impl ModeButton {
 
     pub async fn handle_interaction(
         self,
-        ctx: &Context,
-        mci: &mut MessageComponentInteraction,
+        data: &TypeMap,
+        transport: &dyn Transport,
+        click: &ButtonClick,
     ) -> anyhow::Result<()> {
-        let data = ctx.data.read().await;
-        let player_state = data.get::<PlayerState>().unwrap();
-        let user = mci.user.clone();
-        let game = {
-            let mut lock = player_state.lock().unwrap();
-            lock.games_per_player.get_mut(&user.id.0).cloned()
+        let mut game = match data.get::<GameStore>().unwrap().lock(click.user_id).await {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let locale = {
+            let preference_store = data.get::<PreferenceStore>().unwrap();
+            let lock = preference_store.lock().unwrap();
+            lock.get(click.user_id).locale
         };
-
-        if game.is_none() {
-            return Ok(());
-        }
-        let mut game = game.unwrap();
 
         let change_message = match game.set_strict_mode(self.mode) {
-            Err(ModeChangeError::AlreadySet) => "Requested mode is already set.",
-            Err(ModeChangeError::TooManyGuessesAlready) => {
-                "Cannot switch to strict mode with more than one guess."
-            }
-            Ok(()) if game.get_strict_mode() == StrictMode::Disabled => "Disabled strict mode.",
-            Ok(()) => "Enabled strict mode.",
+            Err(err) => err.localize(locale),
+            Ok(()) => BotMessage::ModeChanged(game.get_strict_mode()).localize(locale),
         };
 
-        mci.create_interaction_response(ctx, |r| {
-            r.interaction_response_data(|msg| {
-                let mut msg_builder = MessageBuilder::new();
-                msg_builder.push_line_safe(change_message);
-                msg.content(msg_builder.build());
-                msg
-            });
-            r
-        })
-        .await?;
+        let mut msg_builder = MessageBuilder::new();
+        msg_builder.push_line_safe(&change_message);
+        transport.respond(click, msg_builder.build()).await?;
 
-        adjust_buttons(mci, &game, ctx).await?;
-
-        {
-            // Save the updated game state.
-            let mut lock = player_state.lock().unwrap();
-            lock.games_per_player.insert(user.id.0, game);
-        }
+        adjust_buttons(transport, click, &game, locale).await?;
 
         Ok(())
     }
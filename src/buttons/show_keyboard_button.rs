@@ -1,22 +1,29 @@
 use serenity::{
     builder::CreateButton,
-    client::Context,
-    model::{
-        channel::ReactionType,
-        interactions::message_component::{ButtonStyle, MessageComponentInteraction},
-    },
+    model::{channel::ReactionType, interactions::message_component::ButtonStyle},
+    prelude::TypeMap,
     utils::MessageBuilder,
 };
 
+use friendle_core::keyboard::KeyboardLayout;
+
 use crate::{
+    game_store::GameStore,
+    i18n::{BotMessage, GameMessage, Locale, Localize},
     model::{
-        evaluation::{get_emoji, EmojiMode},
+        evaluation::{EmojiMode, EmojiTheme, Evaluation},
         game::LetterState,
+        knowledge::{CharacterBound, Knowledge},
     },
-    player::PlayerState,
-    util::{adjust_buttons, get_regional_indicator_emoji_with_zero_width_space, KEYBOARD_LAYOUT},
+    preferences::{DisplayMode, PreferenceStore},
+    transport::{ButtonClick, Transport},
+    util::adjust_buttons,
 };
 
+const ANSI_RESET: &str = "\u{1b}[0m";
+
+const SUPERSCRIPT_DIGITS: &[char] = &['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
 pub struct ShowKeyboardButton {}
 
 impl ShowKeyboardButton {
@@ -24,63 +31,229 @@ impl ShowKeyboardButton {
 }
 
 impl ShowKeyboardButton {
-    pub fn button() -> CreateButton {
+    pub fn button(locale: Locale) -> CreateButton {
         let mut show_keyboard_button = CreateButton::default();
 
         show_keyboard_button.custom_id(Self::ID);
-        show_keyboard_button.label("show keyboard");
+        show_keyboard_button.label(BotMessage::ShowKeyboardLabel.localize(locale));
         show_keyboard_button.style(ButtonStyle::Primary);
         show_keyboard_button.emoji(ReactionType::Unicode(String::from("⌨️")));
         show_keyboard_button
     }
 
     pub async fn handle_interaction(
-        ctx: &Context,
-        mci: &mut MessageComponentInteraction,
+        data: &TypeMap,
+        transport: &dyn Transport,
+        click: &ButtonClick,
     ) -> anyhow::Result<()> {
-        let data = ctx.data.read().await;
-        let player_state = data.get::<PlayerState>().unwrap();
-        let user = &mci.user;
-        let game = {
-            let mut lock = player_state.lock().unwrap();
-            lock.games_per_player.get_mut(&user.id.0).cloned()
+        let game = match data.get::<GameStore>().unwrap().get(click.user_id).await {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let preferences = {
+            let preference_store = data.get::<PreferenceStore>().unwrap();
+            let lock = preference_store.lock().unwrap();
+            lock.get(click.user_id)
         };
 
-        if game.is_none() {
-            return Ok(());
+        let mut msg_builder = MessageBuilder::new();
+        if preferences.display_mode == DisplayMode::ScreenReader {
+            display_keyboard_as_text(game.knowledge(), preferences.locale, &mut msg_builder);
+        } else {
+            display_keyboard(
+                game.knowledge(),
+                preferences.keyboard_layout,
+                &preferences.theme,
+                preferences.locale,
+                &mut msg_builder,
+            );
         }
-        let game = game.unwrap();
-
-        mci.create_interaction_response(ctx, |r| {
-            r.interaction_response_data(|msg| {
-                let mut msg_builder = MessageBuilder::new();
-                for row in KEYBOARD_LAYOUT {
-                    for c in row.chars() {
-                        let state = game.get_letter_state(c);
-                        match state {
-                            LetterState::Unknown => {
-                                msg_builder
-                                    .push(get_regional_indicator_emoji_with_zero_width_space(c));
-                            }
-                            _ => {
-                                msg_builder.push(get_emoji(
-                                    state.to_evaluation().unwrap(),
-                                    EmojiMode::Unicode,
-                                ));
-                            }
-                        }
-                    }
-                    msg_builder.push_line("");
-                }
-                msg.content(msg_builder.build());
-                msg
-            });
-            r
-        })
-        .await?;
+        transport.respond(click, msg_builder.build()).await?;
 
-        adjust_buttons(mci, &game, ctx).await?;
+        adjust_buttons(transport, click, &game, preferences.locale).await?;
 
         Ok(())
     }
 }
+
+fn ansi_color(state: LetterState, theme: &EmojiTheme) -> Option<u8> {
+    state
+        .to_evaluation()
+        .map(|evaluation| theme.ansi_color(evaluation))
+}
+
+/// Annotates letters known to be in the word with their count:
+/// `¹` if the count is known exactly, `¹⁺` if it is a lower bound.
+fn count_annotation(bound: Option<CharacterBound>) -> String {
+    match bound {
+        Some(CharacterBound::Exact(count)) if count > 0 => {
+            format!("{} ", SUPERSCRIPT_DIGITS[count.min(9)])
+        }
+        Some(CharacterBound::Minimum(count)) if count > 0 => {
+            format!("{}⁺", SUPERSCRIPT_DIGITS[count.min(9)])
+        }
+        _ => String::from("  "),
+    }
+}
+
+/// Renders the keyboard as an ANSI code block so the letters stay visible alongside their state.
+fn display_keyboard(
+    knowledge: &Knowledge,
+    layout: KeyboardLayout,
+    theme: &EmojiTheme,
+    locale: Locale,
+    message_builder: &mut MessageBuilder,
+) {
+    message_builder.push_line("```ansi");
+    for (row_index, row) in layout.rows().iter().enumerate() {
+        message_builder.push(" ".repeat(row_index));
+        for c in row.chars() {
+            let state = knowledge.letter_state(c);
+            let letter = c.to_ascii_uppercase();
+            match ansi_color(state, theme) {
+                Some(color) => {
+                    message_builder.push(format!("\u{1b}[1;{color}m{letter}{ANSI_RESET}"));
+                }
+                None => {
+                    message_builder.push(letter);
+                }
+            }
+            message_builder.push(count_annotation(knowledge.character_bound(c)));
+        }
+        message_builder.push_line("");
+    }
+    message_builder.push_line("```");
+    message_builder.push_line(
+        BotMessage::KeyboardLegend {
+            correct: theme.emoji(Evaluation::Correct, EmojiMode::Unicode),
+            present: theme.emoji(Evaluation::Present, EmojiMode::Unicode),
+            absent: theme.emoji(Evaluation::Absent, EmojiMode::Unicode),
+        }
+        .localize(locale),
+    );
+}
+
+/// Lists the letters grouped by state, in alphabetical order, so a screen reader can read them out.
+fn display_keyboard_as_text(
+    knowledge: &Knowledge,
+    locale: Locale,
+    message_builder: &mut MessageBuilder,
+) {
+    let groups = [
+        LetterState::Correct,
+        LetterState::Present,
+        LetterState::Absent,
+        LetterState::Unknown,
+    ];
+    for state in groups {
+        let letters = ('a'..='z')
+            .filter(|c| knowledge.letter_state(*c) == state)
+            .map(|c| match knowledge.character_bound(c) {
+                Some(bound) if bound.count() > 0 => {
+                    format!(
+                        "{} ({})",
+                        c.to_ascii_uppercase(),
+                        GameMessage::LetterCount(bound).localize(locale)
+                    )
+                }
+                _ => c.to_ascii_uppercase().to_string(),
+            })
+            .collect::<Vec<_>>();
+        if !letters.is_empty() {
+            message_builder.push_line(format!(
+                "{}: {}.",
+                BotMessage::KeyboardGroup(state).localize(locale),
+                letters.join(", ")
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::game::Guess;
+
+    #[test]
+    fn test_count_annotation() {
+        assert_eq!(count_annotation(None), "  ");
+        assert_eq!(count_annotation(Some(CharacterBound::Exact(0))), "  ");
+        assert_eq!(count_annotation(Some(CharacterBound::Exact(1))), "¹ ");
+        assert_eq!(count_annotation(Some(CharacterBound::Minimum(2))), "²⁺");
+    }
+
+    /// What is known after guessing "fluff" for the solution "fates".
+    fn fluff_knowledge() -> Knowledge {
+        let mut knowledge = Knowledge::new(5);
+        knowledge.learn(&Guess {
+            word: String::from("fluff"),
+            evaluation: vec![
+                Evaluation::Correct,
+                Evaluation::Absent,
+                Evaluation::Absent,
+                Evaluation::Absent,
+                Evaluation::Absent,
+            ],
+            warning: None,
+        });
+        knowledge
+    }
+
+    fn keyboard(layout: KeyboardLayout, theme: &EmojiTheme) -> String {
+        let mut message_builder = MessageBuilder::new();
+        display_keyboard(
+            &fluff_knowledge(),
+            layout,
+            theme,
+            Locale::En,
+            &mut message_builder,
+        );
+        message_builder.build()
+    }
+
+    #[test]
+    fn test_display_keyboard_qwerty() {
+        let keyboard = keyboard(KeyboardLayout::Qwerty, &EmojiTheme::Classic);
+        let lines = keyboard.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "```ansi");
+        assert!(lines[1].starts_with("Q  W  E  R  T  Y  "));
+        // `f` is green with an exact count, `l` is red.
+        assert!(lines[2].contains("\u{1b}[1;32mF\u{1b}[0m¹ "));
+        assert!(lines[2].contains("\u{1b}[1;31mL\u{1b}[0m  "));
+        assert_eq!(lines[4], "```");
+    }
+
+    #[test]
+    fn test_display_keyboard_azerty() {
+        let keyboard = keyboard(KeyboardLayout::Azerty, &EmojiTheme::Classic);
+        let lines = keyboard.lines().collect::<Vec<_>>();
+        assert!(lines[1].starts_with("A  Z  E  R  T  Y  "));
+        assert!(lines[2].starts_with(" Q  S  D  \u{1b}[1;32mF\u{1b}[0m¹ "));
+    }
+
+    #[test]
+    fn test_display_keyboard_colorblind() {
+        let keyboard = keyboard(KeyboardLayout::Qwerty, &EmojiTheme::Colorblind);
+        // absent letters are gray in the colorblind theme
+        assert!(keyboard.contains("\u{1b}[1;30mL\u{1b}[0m  "));
+        assert!(!keyboard.contains("\u{1b}[1;31m"));
+    }
+
+    #[test]
+    fn test_display_keyboard_as_text() {
+        let knowledge = fluff_knowledge();
+        let mut message_builder = MessageBuilder::new();
+        display_keyboard_as_text(&knowledge, Locale::En, &mut message_builder);
+        let keyboard = message_builder.build();
+        let lines = keyboard.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Correct: F (exactly 1).");
+        assert_eq!(lines[1], "Absent: L, U.");
+        assert!(lines[2].starts_with("Not guessed yet: A, B, C, D, E, G, "));
+
+        let mut message_builder = MessageBuilder::new();
+        display_keyboard_as_text(&knowledge, Locale::De, &mut message_builder);
+        assert!(message_builder
+            .build()
+            .starts_with("Richtig: F (genau 1).\nFehlt: L, U.\n"));
+    }
+}
@@ -0,0 +1,156 @@
+use serenity::{
+    builder::CreateButton,
+    model::{channel::ReactionType, interactions::message_component::ButtonStyle},
+    prelude::TypeMap,
+    utils::MessageBuilder,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    game_store::GameStore,
+    i18n::{BotMessage, GameMessage, Locale, Localize},
+    model::knowledge::{Knowledge, PositionalKnowledgeState},
+    preferences::PreferenceStore,
+    transport::{ButtonClick, Transport},
+    util::adjust_buttons,
+};
+
+pub struct ShowKnowledgeButton {}
+
+impl ShowKnowledgeButton {
+    pub const ID: &'static str = "knowledge";
+}
+
+impl ShowKnowledgeButton {
+    pub fn button(locale: Locale) -> CreateButton {
+        let mut show_knowledge_button = CreateButton::default();
+
+        show_knowledge_button.custom_id(Self::ID);
+        show_knowledge_button.label(BotMessage::ShowKnowledgeLabel.localize(locale));
+        show_knowledge_button.style(ButtonStyle::Primary);
+        show_knowledge_button.emoji(ReactionType::Unicode(String::from("🧠")));
+        show_knowledge_button
+    }
+
+    pub async fn handle_interaction(
+        data: &TypeMap,
+        transport: &dyn Transport,
+        click: &ButtonClick,
+    ) -> anyhow::Result<()> {
+        let game = match data.get::<GameStore>().unwrap().get(click.user_id).await {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let locale = {
+            let preference_store = data.get::<PreferenceStore>().unwrap();
+            let lock = preference_store.lock().unwrap();
+            lock.get(click.user_id).locale
+        };
+
+        let mut msg_builder = MessageBuilder::new();
+        display_knowledge(game.knowledge(), locale, &mut msg_builder);
+        transport.respond(click, msg_builder.build()).await?;
+
+        adjust_buttons(transport, click, &game, locale).await?;
+
+        Ok(())
+    }
+}
+
+/// Renders a human-readable summary of everything known about the solution.
+fn display_knowledge(knowledge: &Knowledge, locale: Locale, message_builder: &mut MessageBuilder) {
+    let pattern = knowledge
+        .positions()
+        .iter()
+        .map(|position| match position.state() {
+            PositionalKnowledgeState::FixedLetter(c) => c.to_ascii_uppercase(),
+            PositionalKnowledgeState::IncorrectLetters(_) => '_',
+        })
+        .join(" ");
+    message_builder.push_line(BotMessage::KnowledgePattern(&pattern).localize(locale));
+
+    let absent_letters = knowledge.absent_letters();
+    for position in knowledge.positions() {
+        if let PositionalKnowledgeState::IncorrectLetters(incorrect_letters) = position.state() {
+            // Absent letters are listed separately below.
+            let excluded = incorrect_letters
+                .iter()
+                .filter(|c| !absent_letters.contains(c))
+                .sorted()
+                .map(|c| format!("`{c}`"))
+                .join(", ");
+            if !excluded.is_empty() {
+                message_builder.push_line(
+                    BotMessage::NotInPosition {
+                        position: position.index() + 1,
+                        letters: &excluded,
+                    }
+                    .localize(locale),
+                );
+            }
+        }
+    }
+
+    let required_letters = knowledge.required_letters();
+    if !required_letters.is_empty() {
+        let required = required_letters
+            .iter()
+            .map(|(c, bound)| {
+                format!(
+                    "`{c}` ({})",
+                    GameMessage::LetterCount(*bound).localize(locale)
+                )
+            })
+            .join(", ");
+        message_builder.push_line(BotMessage::RequiredLetters(&required).localize(locale));
+    }
+
+    if !absent_letters.is_empty() {
+        let absent = absent_letters.iter().map(|c| format!("`{c}`")).join(", ");
+        message_builder.push_line(BotMessage::AbsentLetters(&absent).localize(locale));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{evaluation::Evaluation, game::Guess};
+
+    #[test]
+    fn test_display_knowledge() {
+        let mut knowledge = Knowledge::new(5);
+        // solution: "tales"
+        knowledge.learn(&Guess {
+            word: String::from("value"),
+            evaluation: vec![
+                Evaluation::Absent,
+                Evaluation::Correct,
+                Evaluation::Correct,
+                Evaluation::Absent,
+                Evaluation::Present,
+            ],
+            warning: None,
+        });
+
+        let mut message_builder = MessageBuilder::new();
+        display_knowledge(&knowledge, Locale::En, &mut message_builder);
+        assert_eq!(
+            message_builder.build(),
+            "Pattern: `_ A L _ _`\n\
+            Not in position 5: `e`\n\
+            Required letters: `a` (at least 1), `e` (at least 1), `l` (at least 1)\n\
+            Absent letters: `u`, `v`\n"
+        );
+
+        let mut message_builder = MessageBuilder::new();
+        display_knowledge(&knowledge, Locale::De, &mut message_builder);
+        assert_eq!(
+            message_builder.build(),
+            "Muster: `_ A L _ _`\n\
+            Nicht an Position 5: `e`\n\
+            Enthaltene Buchstaben: `a` (mindestens 1), `e` (mindestens 1), `l` (mindestens 1)\n\
+            Fehlende Buchstaben: `u`, `v`\n"
+        );
+    }
+}
@@ -3,9 +3,15 @@ use serde::{Deserialize, Serialize};
 use serenity::client::Context;
 use serenity::framework::standard::{macros::command, CommandResult};
 use serenity::model::channel::Message;
+use tracing::{error, warn};
 
+use crate::commands::encode::validate_encode_and_post;
+use crate::config::Config;
+use crate::i18n::{BotMessage, Localize};
+use crate::metrics::Metrics;
+use crate::preferences::PreferenceStore;
+use crate::transport::DiscordTransport;
 use crate::util::extract_second_word;
-use crate::validate_encode_and_post;
 
 #[derive(Deserialize, Serialize, Debug)]
 struct Daily {
@@ -28,30 +34,47 @@ pub async fn daily(ctx: &Context, msg: &Message) -> CommandResult {
         }
     };
 
-    match get_daily_wordle_solution(date).await {
+    let url = ctx
+        .data
+        .read()
+        .await
+        .get::<Config>()
+        .unwrap()
+        .daily_url(date);
+    match get_daily_wordle_solution(&url).await {
         Err(e) => {
-            eprintln!("{e}");
+            warn!(date, "Failed to fetch the daily Wordle solution: {e}");
+            ctx.data
+                .read()
+                .await
+                .get::<Metrics>()
+                .unwrap()
+                .daily_fetch_failed();
+            let locale = {
+                let data = ctx.data.read().await;
+                let preference_store = data.get::<PreferenceStore>().unwrap();
+                let lock = preference_store.lock().unwrap();
+                lock.get(msg.author.id.0).locale
+            };
             if let Err(e2) = msg
-                .reply(
-                    ctx,
-                    format!("could not acquire Wordle solution for `{date}`"),
-                )
+                .reply(ctx, BotMessage::DailyUnavailable(date).localize(locale))
                 .await
             {
-                eprintln!("Error posting about problem ({e}) acquiring daily wordle solution: {e2}");
+                error!("Error posting about problem ({e}) acquiring daily wordle solution: {e2}");
             }
         }
 
         Ok(daily) => {
-            validate_encode_and_post(ctx, msg, &daily.solution).await?;
+            let data = ctx.data.read().await;
+            let transport = DiscordTransport::from(ctx);
+            validate_encode_and_post(&data, &transport, &msg.into(), &daily.solution).await?;
         }
     }
 
     Ok(())
 }
 
-async fn get_daily_wordle_solution(date: &str) -> anyhow::Result<Daily> {
-    let url = format!("https://www.nytimes.com/svc/wordle/v2/{}.json", date);
+async fn get_daily_wordle_solution(url: &str) -> anyhow::Result<Daily> {
     let response = get(url).await?.text().await?;
     let daily: Daily = serde_json::from_str(&response)?;
     Ok(daily)
@@ -0,0 +1,49 @@
+use crate::i18n::{BotMessage, Localize};
+use crate::preferences::{DisplayMode, PreferenceStore};
+use crate::util::extract_second_word;
+
+use itertools::Itertools;
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+
+#[command]
+#[description = "Choose how the board is shown after each guess: \
+`image`, `text` or `screenreader`. \
+The screen reader mode also applies to the keyboard and shared results."]
+pub async fn display(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let user_id = msg.author.id.0;
+    let available = DisplayMode::ALL
+        .iter()
+        .map(|mode| format!("`{}`", mode.name()))
+        .join(", ");
+    let current = preference_store.lock().unwrap().get(user_id);
+    let locale = current.locale;
+
+    let reply = match extract_second_word(&msg.content) {
+        None => BotMessage::CurrentDisplayMode {
+            current: current.display_mode,
+            available: &available,
+        }
+        .localize(locale),
+        Some(name) => match name.parse::<DisplayMode>() {
+            Ok(mode) => {
+                preference_store
+                    .lock()
+                    .unwrap()
+                    .update(user_id, |preferences| preferences.display_mode = mode);
+                BotMessage::DisplayModeChanged(mode).localize(locale)
+            }
+            Err(_) => BotMessage::UnknownDisplayMode {
+                name,
+                available: &available,
+            }
+            .localize(locale),
+        },
+    };
+
+    msg.reply(ctx, reply).await?;
+    Ok(())
+}
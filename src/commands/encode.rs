@@ -1,48 +1,84 @@
+use crate::config::Config;
+use crate::i18n::{BotMessage, Locale, Localize};
 use crate::model::validate_word::validate_word_format;
+use crate::preferences::PreferenceStore;
+use crate::stats::CodeStatsStore;
+use crate::transport::{DiscordTransport, IncomingMessage, Transport};
 use crate::util::extract_second_word;
 use crate::wordlist::WordList;
 use serenity::client::Context;
 use serenity::framework::standard::{macros::command, CommandResult};
 use serenity::model::channel::Message;
+use serenity::prelude::TypeMap;
 
 #[command]
 #[description = "Encode a word as a secret for Wordle."]
 #[only_in(dm)]
 pub async fn encode(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    handle_encode(&data, &DiscordTransport::from(ctx), &msg.into()).await?;
+    Ok(())
+}
+
+pub async fn handle_encode(
+    data: &TypeMap,
+    transport: &dyn Transport,
+    msg: &IncomingMessage,
+) -> anyhow::Result<()> {
     if let Some(word) = extract_second_word(&msg.content) {
-        validate_encode_and_post(ctx, msg, word).await?;
+        validate_encode_and_post(data, transport, msg, word).await?;
     } else {
-        msg.reply(ctx, "Please provide a word.").await?;
+        let locale = user_locale(data, msg.author_id);
+        transport
+            .reply(msg, BotMessage::MissingWord.localize(locale))
+            .await?;
     }
     Ok(())
 }
 
-pub async fn validate_encode_and_post (ctx: &Context, msg: &Message, word: &str) -> CommandResult {
-
-        match validate_word_format(word) {
-            Err(_) => {
-                msg.reply(
-                    ctx,
-                    format!("Invalid word format: {word}. Only five-letter words with letters a-z are supported."),
-                )
+pub async fn validate_encode_and_post(
+    data: &TypeMap,
+    transport: &dyn Transport,
+    msg: &IncomingMessage,
+    word: &str,
+) -> anyhow::Result<()> {
+    let locale = user_locale(data, msg.author_id);
+    match validate_word_format(word) {
+        Err(_) => {
+            transport
+                .reply(msg, BotMessage::InvalidWordFormat(word).localize(locale))
                 .await?;
-            }
-            Ok(_) => {
-                let code = crate::model::coding::encode(word);
-                let value = code.value;
+        }
+        Ok(_) => {
+            let code = crate::model::coding::encode(word);
+            let value = code.value;
 
-                let data = ctx.data.read().await;
-                let word_list = data.get::<WordList>().unwrap();
-                let mut reply = String::from(
-                    "To play a game of Wordle with your secret word, use the following command.\n",
-                );
-                if !word_list.words.contains(word) {
-                    reply += "Note that your word is not in the original Wordle word list.";
-                };
+            let word_list = data.get::<WordList>().unwrap();
+            let difficulty = word_list.difficulty(word);
+            data.get::<CodeStatsStore>()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .get_or_insert(value, || difficulty);
 
-                msg.reply(ctx, reply).await?;
-                msg.reply(ctx, format!(".play `{value}`")).await?;
-            }
+            let mut reply = BotMessage::ShareEncodedWord.localize(locale);
+            reply += "\n";
+            reply += &BotMessage::EstimatedDifficulty(difficulty).localize(locale);
+            reply += "\n";
+            if !word_list.words.contains(word) {
+                reply += &BotMessage::WordNotInWordList.localize(locale);
+            };
+
+            transport.reply(msg, reply).await?;
+            let play = data.get::<Config>().unwrap().command("play");
+            transport.reply(msg, format!("{play} `{value}`")).await?;
         }
+    }
     Ok(())
 }
+
+fn user_locale(data: &TypeMap, user_id: u64) -> Locale {
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let lock = preference_store.lock().unwrap();
+    lock.get(user_id).locale
+}
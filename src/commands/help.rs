@@ -1,3 +1,7 @@
+use crate::config::Config;
+use crate::i18n::{BotMessage, Locale, Localize};
+use crate::preferences::PreferenceStore;
+
 use serenity::client::Context;
 use serenity::model::channel::Message;
 use serenity::model::id::UserId;
@@ -24,6 +28,24 @@ async fn my_help(
     groups: &[&'static CommandGroup],
     owners: HashSet<UserId>,
 ) -> CommandResult {
+    let (locale, prefix) = {
+        let data = context.data.read().await;
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        (
+            lock.get(msg.author.id.0).locale,
+            data.get::<Config>().unwrap().prefix.clone(),
+        )
+    };
+    // Command descriptions are only available in English, so other languages get a translated overview instead.
+    if locale != Locale::En && args.is_empty() {
+        msg.reply(
+            context,
+            BotMessage::Help { prefix: &prefix }.localize(locale),
+        )
+        .await?;
+        return Ok(());
+    }
     let _ = help_commands::with_embeds(context, msg, args, help_options, groups, owners).await;
     Ok(())
 }
@@ -0,0 +1,38 @@
+use crate::i18n::{BotMessage, Locale, Localize};
+use crate::preferences::PreferenceStore;
+use crate::util::extract_second_word;
+
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+
+#[command]
+#[aliases("sprache")]
+#[description = "Choose the language the bot talks to you in: `en` or `de`."]
+pub async fn language(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let user_id = msg.author.id.0;
+    let current = preference_store.lock().unwrap().get(user_id).locale;
+
+    let reply = match extract_second_word(&msg.content) {
+        None => BotMessage::CurrentLocale(current).localize(current),
+        Some(name) => match name.parse::<Locale>() {
+            Ok(locale) => {
+                preference_store
+                    .lock()
+                    .unwrap()
+                    .update(user_id, |preferences| preferences.locale = locale);
+                BotMessage::LocaleChanged(locale).localize(locale)
+            }
+            Err(_) => format!(
+                "{} {}",
+                BotMessage::UnknownLocale(name).localize(current),
+                BotMessage::CurrentLocale(current).localize(current)
+            ),
+        },
+    };
+
+    msg.reply(ctx, reply).await?;
+    Ok(())
+}
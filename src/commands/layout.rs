@@ -0,0 +1,49 @@
+use crate::i18n::{BotMessage, Localize};
+use crate::preferences::PreferenceStore;
+use crate::util::extract_second_word;
+use friendle_core::keyboard::KeyboardLayout;
+
+use itertools::Itertools;
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+
+#[command]
+#[description = "Choose the keyboard layout used by the keyboard button: \
+`qwerty`, `azerty`, `qwertz`, `dvorak` or `alphabetical`."]
+pub async fn layout(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let user_id = msg.author.id.0;
+    let available = KeyboardLayout::ALL
+        .iter()
+        .map(|layout| format!("`{}`", layout.name()))
+        .join(", ");
+    let current = preference_store.lock().unwrap().get(user_id);
+    let locale = current.locale;
+
+    let reply = match extract_second_word(&msg.content) {
+        None => BotMessage::CurrentLayout {
+            current: current.keyboard_layout,
+            available: &available,
+        }
+        .localize(locale),
+        Some(name) => match name.parse::<KeyboardLayout>() {
+            Ok(layout) => {
+                preference_store
+                    .lock()
+                    .unwrap()
+                    .update(user_id, |preferences| preferences.keyboard_layout = layout);
+                BotMessage::LayoutChanged(layout).localize(locale)
+            }
+            Err(_) => BotMessage::UnknownLayout {
+                name,
+                available: &available,
+            }
+            .localize(locale),
+        },
+    };
+
+    msg.reply(ctx, reply).await?;
+    Ok(())
+}
@@ -1,4 +1,12 @@
+pub mod daily;
+pub mod display;
 pub mod encode;
 pub mod help;
+pub mod language;
+pub mod layout;
 pub mod play;
-pub mod daily;
+pub mod random;
+pub mod settings;
+pub mod stats;
+pub mod strict;
+pub mod theme;
@@ -1,53 +1,65 @@
 use crate::buttons::mode_button::ModeButton;
+use crate::buttons::FriendleButton;
+use crate::i18n::{BotMessage, Localize};
+use crate::metrics::Metrics;
 use crate::model::coding::{decode, Code};
 
-use crate::model::game::{Game, GameFlag, GameFlags, GameState, StrictMode};
+use crate::model::game::{Game, GameFlag, GameFlags, StrictMode};
 
-use crate::player::PlayerState;
+use crate::game_store::GameStore;
+use crate::preferences::PreferenceStore;
+use crate::stats::CodeStatsStore;
+use crate::transport::{DiscordTransport, IncomingMessage, OutgoingMessage, Transport};
 use crate::util::extract_second_word;
 use crate::wordlist::WordList;
 
 use serenity::client::Context;
 use serenity::framework::standard::{macros::command, CommandResult};
 use serenity::model::channel::Message;
+use serenity::prelude::TypeMap;
 use serenity::utils::MessageBuilder;
 
 use std::collections::HashSet;
-use std::sync::{Arc, Mutex};
+use tracing::error;
 
-enum GameCreationState {
+pub enum GameCreationState {
     AlreadyInProgress,
-    SuccessfullyCreated(GameFlags),
+    SuccessfullyCreated {
+        flags: GameFlags,
+        strict_mode: StrictMode,
+        mode_switch_options: Vec<StrictMode>,
+    },
     ErrorDuringCreation,
 }
 
-fn construct_game_opt_result(
-    player_state: &Arc<Mutex<PlayerState>>,
+pub async fn construct_game_opt_result(
+    games: &GameStore,
     player_id: u64,
     code: Code,
     solution: String,
     word_list: &HashSet<String>,
+    strict_mode: StrictMode,
 ) -> GameCreationState {
-    let mut lock = player_state.lock().unwrap();
-    let game = lock.games_per_player.get(&player_id);
-
-    if let Some(game) = game {
-        // Report the game as in-progress only if it has not been finished yet.
-        // If the game has already finished, we're free to start a new one.
-        if game.state() == GameState::InProgress {
-            return GameCreationState::AlreadyInProgress;
-        }
-    }
-
     match Game::new(code, solution, word_list) {
-        Ok(game) => {
-            let flags = game.flags().clone();
-            // We could use the entry API above, but Entry::insert_entry is nightly-only, so we'd have to call HashMap::insert anyway.
-            lock.games_per_player.insert(player_id, game);
-            GameCreationState::SuccessfullyCreated(flags)
+        Ok(mut game) => {
+            if strict_mode != game.get_strict_mode() {
+                // New games don't have any guesses yet, so every mode is available.
+                game.set_strict_mode(strict_mode).unwrap();
+            }
+            let state = GameCreationState::SuccessfullyCreated {
+                flags: game.flags().clone(),
+                strict_mode: game.get_strict_mode(),
+                mode_switch_options: game.mode_switch_options(),
+            };
+            // Only report the game as in-progress if it has not been finished yet.
+            // If the game has already finished, we're free to start a new one.
+            if !games.start(player_id, game).await {
+                return GameCreationState::AlreadyInProgress;
+            }
+            state
         }
         Err(err) => {
-            eprintln!("Error during game creation: {err}");
+            error!(code = code.value, "Error during game creation: {err}");
             GameCreationState::ErrorDuringCreation
         }
     }
@@ -57,62 +69,159 @@ fn construct_game_opt_result(
 #[description = "Play a round of Wordle."]
 #[only_in(dm)]
 pub async fn play(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    handle_play(&data, &DiscordTransport::from(ctx), &msg.into()).await?;
+    Ok(())
+}
+
+pub async fn handle_play(
+    data: &TypeMap,
+    transport: &dyn Transport,
+    msg: &IncomingMessage,
+) -> anyhow::Result<()> {
     // remove back ticks since we escape back ticks when showing the code.
     if let Some(code) = extract_second_word(&msg.content.replace('`', "")) {
         match code.parse::<u32>() {
             Ok(code) => {
                 if let Some(word) = decode(Code { value: code }) {
-                    let player_id = msg.author.id.0;
-                    let data = ctx.data.read().await;
-                    let player_state = data.get::<PlayerState>().unwrap();
-                    let word_list = data.get::<WordList>().unwrap();
-                    let game_creation_state = construct_game_opt_result(
-                        player_state,
-                        player_id,
-                        Code { value: code },
-                        word,
-                        &word_list.words,
-                    );
-
-                    match game_creation_state {
-                        GameCreationState::AlreadyInProgress => {
-                            msg.reply(ctx, "Game aleady in progress!").await?;
-                        }
-                        GameCreationState::ErrorDuringCreation => {
-                            msg.reply(ctx, String::from("Encountered an internal error."))
-                                .await?;
-                        }
-                        GameCreationState::SuccessfullyCreated(flags) => {
-                            let mut msg_builder = MessageBuilder::new();
-                            msg_builder.push_line("You can now start guessing. Good luck.");
-
-                            if flags.contains(&GameFlag::SolutionNotInWordList) {
-                                msg_builder.push_line("Btw, the solution to this one is not in the original word list.");
-                            }
-
-                            msg.channel_id
-                                .send_message(ctx, |m| {
-                                    m.content(msg_builder);
-                                    m.components(|comps| {
-                                        comps.create_action_row(|row| {
-                                            row.add_button(
-                                                ModeButton::new(StrictMode::Enabled).mode_button(),
-                                            );
-                                            row
-                                        });
-                                        comps
-                                    });
-                                    m
-                                })
-                                .await?;
-                        }
-                    }
+                    start_game(data, transport, msg, Code { value: code }, word).await?;
                 }
             }
             Err(_) => {
-                msg.reply(ctx, format!("Invalid code: {code}")).await?;
+                let locale = {
+                    let preference_store = data.get::<PreferenceStore>().unwrap();
+                    let lock = preference_store.lock().unwrap();
+                    lock.get(msg.author_id).locale
+                };
+                transport
+                    .reply(msg, BotMessage::InvalidCode(code).localize(locale))
+                    .await?;
             }
         }
     }
     Ok(())
 }
+
+/// Starts a new game for the author of `msg` and posts the intro message.
+/// Returns whether the game was created successfully.
+pub async fn start_game(
+    data: &TypeMap,
+    transport: &dyn Transport,
+    msg: &IncomingMessage,
+    code: Code,
+    word: String,
+) -> anyhow::Result<bool> {
+    let player_id = msg.author_id;
+    let games = data.get::<GameStore>().unwrap();
+    let word_list = data.get::<WordList>().unwrap();
+    let difficulty = word_list.difficulty(&word);
+    let preferences = {
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        lock.get(player_id)
+    };
+    let locale = preferences.locale;
+    let game_creation_state = construct_game_opt_result(
+        games,
+        player_id,
+        code,
+        word,
+        &word_list.words,
+        preferences.default_strict_mode,
+    )
+    .await;
+
+    match game_creation_state {
+        GameCreationState::AlreadyInProgress => {
+            transport
+                .reply(msg, BotMessage::GameAlreadyInProgress.localize(locale))
+                .await?;
+            Ok(false)
+        }
+        GameCreationState::ErrorDuringCreation => {
+            transport
+                .reply(msg, BotMessage::InternalError.localize(locale))
+                .await?;
+            Ok(false)
+        }
+        GameCreationState::SuccessfullyCreated {
+            flags,
+            strict_mode,
+            mode_switch_options,
+        } => {
+            let difficulty = {
+                let mut stats = data.get::<CodeStatsStore>().unwrap().lock().unwrap();
+                let code_stats = stats.get_or_insert(code.value, || difficulty);
+                code_stats.games_started += 1;
+                code_stats.difficulty
+            };
+            data.get::<Metrics>().unwrap().game_started(strict_mode);
+
+            let mut msg_builder = MessageBuilder::new();
+            msg_builder.push_line(BotMessage::StartGuessing.localize(locale));
+            msg_builder.push_line(BotMessage::EstimatedDifficulty(difficulty).localize(locale));
+            if strict_mode != StrictMode::Disabled {
+                msg_builder.push_line(BotMessage::DefaultStrictMode(strict_mode).localize(locale));
+            }
+
+            if flags.contains(&GameFlag::SolutionNotInWordList) {
+                msg_builder.push_line(BotMessage::SolutionNotInWordList.localize(locale));
+            }
+
+            transport
+                .send_message(
+                    msg.channel_id,
+                    OutgoingMessage {
+                        content: msg_builder.build(),
+                        image: None,
+                        buttons: vec![mode_switch_options
+                            .into_iter()
+                            .map(|mode| FriendleButton::ModeChangeButton(ModeButton::new(mode)))
+                            .collect()],
+                        locale,
+                    },
+                )
+                .await?;
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::coding::encode;
+
+    #[tokio::test]
+    async fn test_default_strict_mode() {
+        let games = GameStore::default();
+        let word_list = HashSet::from_iter(std::iter::once(String::from("tales")));
+
+        let state = construct_game_opt_result(
+            &games,
+            1,
+            encode("tales"),
+            String::from("tales"),
+            &word_list,
+            StrictMode::Enabled,
+        )
+        .await;
+        match state {
+            GameCreationState::SuccessfullyCreated {
+                strict_mode,
+                mode_switch_options,
+                ..
+            } => {
+                assert_eq!(strict_mode, StrictMode::Enabled);
+                // players can still opt out of their default.
+                assert!(mode_switch_options.contains(&StrictMode::Disabled));
+                assert!(!mode_switch_options.contains(&StrictMode::Enabled));
+            }
+            _ => panic!("game should have been created"),
+        }
+        assert_eq!(
+            games.get(1).await.unwrap().get_strict_mode(),
+            StrictMode::Enabled
+        );
+    }
+}
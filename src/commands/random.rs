@@ -0,0 +1,68 @@
+use crate::commands::play::start_game;
+use crate::config::Config;
+use crate::i18n::{BotMessage, Localize};
+use crate::model::coding::{encode, Code};
+use crate::model::difficulty::Difficulty;
+use crate::preferences::PreferenceStore;
+use crate::transport::DiscordTransport;
+use crate::wordlist::{WordFilter, WordList};
+
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+
+#[command]
+#[description = "Play a round of Wordle with a random word from the word list. \
+Add `unique` to only pick words without repeated letters, \
+or one of `easy`, `medium`, `hard` or `evil` to pick a word of that difficulty."]
+#[only_in(dm)]
+pub async fn random(ctx: &Context, msg: &Message) -> CommandResult {
+    let locale = {
+        let data = ctx.data.read().await;
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        lock.get(msg.author.id.0).locale
+    };
+    let mut filter = WordFilter::default();
+    for option in msg.content.split_ascii_whitespace().skip(1) {
+        match option.to_ascii_lowercase().as_str() {
+            "unique" | "norepeat" => filter.unique_letters = true,
+            option => match option.parse::<Difficulty>() {
+                Ok(difficulty) => filter.difficulty = Some(difficulty),
+                Err(_) => {
+                    msg.reply(
+                        ctx,
+                        BotMessage::UnknownRandomOption(option).localize(locale),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            },
+        }
+    }
+
+    let word = {
+        let data = ctx.data.read().await;
+        let word_list = data.get::<WordList>().unwrap();
+        word_list.random_word(filter).map(String::from)
+    };
+
+    let word = match word {
+        Some(word) => word,
+        None => {
+            msg.reply(ctx, BotMessage::NoMatchingWord.localize(locale))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let code: Code = encode(&word);
+    let data = ctx.data.read().await;
+    if start_game(&data, &DiscordTransport::from(ctx), &msg.into(), code, word).await? {
+        msg.reply(ctx, BotMessage::ShareRandomWord.localize(locale))
+            .await?;
+        let play = data.get::<Config>().unwrap().command("play");
+        msg.reply(ctx, format!("{play} `{}`", code.value)).await?;
+    }
+    Ok(())
+}
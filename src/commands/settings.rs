@@ -0,0 +1,228 @@
+use crate::config::Config;
+use crate::i18n::{BotMessage, Localize};
+use crate::model::evaluation::{EmojiMode, EmojiTheme};
+use crate::preferences::{DisplayMode, PreferenceStore, UserPreferences};
+use friendle_core::keyboard::KeyboardLayout;
+
+use serenity::builder::{CreateComponents, CreateSelectMenu};
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+use serenity::model::interactions::message_component::MessageComponentInteraction;
+use serenity::model::interactions::InteractionResponseType;
+use std::str::FromStr;
+
+/// Select menus of the `.settings` message, one per preference.
+/// Messages are limited to five menus; the default strict mode is set with `.strict` instead.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SettingsMenu {
+    KeyboardLayout,
+    Theme,
+    DisplayMode,
+    BoardEmojiMode,
+    ResultEmojiMode,
+}
+
+impl SettingsMenu {
+    pub const ALL: [SettingsMenu; 5] = [
+        SettingsMenu::KeyboardLayout,
+        SettingsMenu::Theme,
+        SettingsMenu::DisplayMode,
+        SettingsMenu::BoardEmojiMode,
+        SettingsMenu::ResultEmojiMode,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            SettingsMenu::KeyboardLayout => "settings_layout",
+            SettingsMenu::Theme => "settings_theme",
+            SettingsMenu::DisplayMode => "settings_display",
+            SettingsMenu::BoardEmojiMode => "settings_board_emojis",
+            SettingsMenu::ResultEmojiMode => "settings_result_emojis",
+        }
+    }
+
+    /// The values users can pick, and the value currently selected.
+    fn options(self, preferences: &UserPreferences) -> (Vec<&'static str>, &'static str) {
+        match self {
+            SettingsMenu::KeyboardLayout => (
+                KeyboardLayout::ALL
+                    .iter()
+                    .map(|layout| layout.name())
+                    .collect(),
+                preferences.keyboard_layout.name(),
+            ),
+            // custom themes need three emojis and can only be set with `.theme custom`.
+            SettingsMenu::Theme => (
+                EmojiTheme::BUILT_IN
+                    .iter()
+                    .map(|theme| theme.name())
+                    .collect(),
+                preferences.theme.name(),
+            ),
+            SettingsMenu::DisplayMode => (
+                DisplayMode::ALL.iter().map(|mode| mode.name()).collect(),
+                preferences.display_mode.name(),
+            ),
+            SettingsMenu::BoardEmojiMode => (
+                EmojiMode::ALL.iter().map(|mode| mode.name()).collect(),
+                preferences.board_emoji_mode.name(),
+            ),
+            SettingsMenu::ResultEmojiMode => (
+                EmojiMode::ALL.iter().map(|mode| mode.name()).collect(),
+                preferences.result_emoji_mode.name(),
+            ),
+        }
+    }
+
+    fn apply(self, value: &str, preferences: &mut UserPreferences) -> Result<(), ()> {
+        let emoji_mode = || {
+            EmojiMode::ALL
+                .into_iter()
+                .find(|mode| mode.name() == value)
+                .ok_or(())
+        };
+        match self {
+            SettingsMenu::KeyboardLayout => preferences.keyboard_layout = value.parse()?,
+            SettingsMenu::Theme => {
+                preferences.theme = EmojiTheme::BUILT_IN
+                    .into_iter()
+                    .find(|theme| theme.name() == value)
+                    .ok_or(())?
+            }
+            SettingsMenu::DisplayMode => preferences.display_mode = value.parse()?,
+            SettingsMenu::BoardEmojiMode => preferences.board_emoji_mode = emoji_mode()?,
+            SettingsMenu::ResultEmojiMode => preferences.result_emoji_mode = emoji_mode()?,
+        }
+        Ok(())
+    }
+
+    fn select_menu(self, preferences: &UserPreferences) -> CreateSelectMenu {
+        let (values, selected) = self.options(preferences);
+        let placeholder = BotMessage::SettingsPlaceholder(self).localize(preferences.locale);
+        let mut menu = CreateSelectMenu::default();
+        menu.custom_id(self.id());
+        menu.placeholder(&placeholder);
+        menu.options(|options| {
+            for value in values {
+                options.create_option(|option| {
+                    option.label(format!("{placeholder}: {value}"));
+                    option.value(value);
+                    option.default_selection(value == selected)
+                });
+            }
+            options
+        });
+        menu
+    }
+
+    /// Applies the value picked in the select menu and updates the settings message.
+    pub async fn handle_interaction(
+        self,
+        ctx: &Context,
+        mci: &mut MessageComponentInteraction,
+    ) -> anyhow::Result<()> {
+        let data = ctx.data.read().await;
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let user_id = mci.user.id.0;
+        let value = mci.data.values.first().cloned().unwrap_or_default();
+        let prefix = &data.get::<Config>().unwrap().prefix;
+
+        let preferences = {
+            let mut lock = preference_store.lock().unwrap();
+            let mut preferences = lock.get(user_id);
+            if self.apply(&value, &mut preferences).is_err() {
+                anyhow::bail!("Unknown value `{value}` for {self:?}");
+            }
+            lock.update(user_id, |stored| *stored = preferences.clone());
+            preferences
+        };
+
+        mci.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|msg| {
+                msg.content(settings_summary(&preferences, prefix));
+                msg.components(|components| add_settings_menus(components, &preferences))
+            })
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+impl FromStr for SettingsMenu {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SettingsMenu::ALL
+            .into_iter()
+            .find(|menu| menu.id() == s)
+            .ok_or(())
+    }
+}
+
+fn settings_summary(preferences: &UserPreferences, prefix: &str) -> String {
+    BotMessage::SettingsSummary {
+        preferences,
+        prefix,
+    }
+    .localize(preferences.locale)
+}
+
+fn add_settings_menus<'a>(
+    components: &'a mut CreateComponents,
+    preferences: &UserPreferences,
+) -> &'a mut CreateComponents {
+    // Discord allows at most five action rows per message, which is exactly one per menu.
+    for menu in SettingsMenu::ALL {
+        components.create_action_row(|row| row.add_select_menu(menu.select_menu(preferences)));
+    }
+    components
+}
+
+#[command]
+#[description = "Show your settings and change them with select menus."]
+pub async fn settings(ctx: &Context, msg: &Message) -> CommandResult {
+    let (preferences, prefix) = {
+        let data = ctx.data.read().await;
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        (
+            lock.get(msg.author.id.0),
+            data.get::<Config>().unwrap().prefix.clone(),
+        )
+    };
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.reference_message(msg);
+            m.content(settings_summary(&preferences, &prefix));
+            m.components(|components| add_settings_menus(components, &preferences))
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_menus() {
+        for menu in SettingsMenu::ALL {
+            assert_eq!(menu.id().parse(), Ok(menu));
+
+            // every offered value can be applied and is then shown as selected
+            let (values, _) = menu.options(&UserPreferences::default());
+            for value in values {
+                let mut preferences = UserPreferences::default();
+                menu.apply(value, &mut preferences).unwrap();
+                assert_eq!(menu.options(&preferences).1, value, "{menu:?}");
+            }
+            assert!(menu
+                .apply("unknown", &mut UserPreferences::default())
+                .is_err());
+        }
+        assert!("keyboard".parse::<SettingsMenu>().is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::i18n::{BotMessage, Localize};
+use crate::model::coding::{decode, Code};
+use crate::preferences::PreferenceStore;
+use crate::stats::CodeStatsStore;
+use crate::util::extract_second_word;
+use crate::wordlist::WordList;
+
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+use serenity::utils::MessageBuilder;
+
+#[command]
+#[description = "Show the difficulty and statistics for a code."]
+pub async fn stats(ctx: &Context, msg: &Message) -> CommandResult {
+    let locale = {
+        let data = ctx.data.read().await;
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        lock.get(msg.author.id.0).locale
+    };
+    let code = match extract_second_word(&msg.content.replace('`', ""))
+        .and_then(|code| code.parse::<u32>().ok())
+    {
+        Some(code) => code,
+        None => {
+            msg.reply(ctx, BotMessage::MissingCode.localize(locale))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let word = match decode(Code { value: code }) {
+        Some(word) => word,
+        None => {
+            msg.reply(
+                ctx,
+                BotMessage::InvalidCode(&code.to_string()).localize(locale),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let mut msg_builder = MessageBuilder::new();
+    {
+        let data = ctx.data.read().await;
+        let word_list = data.get::<WordList>().unwrap();
+        let stats_store = data.get::<CodeStatsStore>().unwrap();
+        let stats = stats_store
+            .lock()
+            .unwrap()
+            .get_or_default(code, || word_list.difficulty(&word));
+
+        msg_builder.push_line(
+            BotMessage::CodeDifficulty {
+                code,
+                difficulty: stats.difficulty,
+            }
+            .localize(locale),
+        );
+        if stats.games_started == 0 {
+            msg_builder.push_line(BotMessage::NobodyPlayedYet.localize(locale));
+        } else {
+            msg_builder.push_line(
+                BotMessage::CodeStats {
+                    started: stats.games_started,
+                    won: stats.games_won,
+                    lost: stats.games_lost,
+                }
+                .localize(locale),
+            );
+            for (i, wins) in stats.guess_distribution.iter().enumerate() {
+                msg_builder.push_line(format!("{}: {wins}", i + 1));
+            }
+        }
+    }
+
+    msg.reply(ctx, msg_builder.build()).await?;
+    Ok(())
+}
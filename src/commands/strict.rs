@@ -0,0 +1,52 @@
+use crate::i18n::{BotMessage, Localize};
+use crate::model::game::StrictMode;
+use crate::preferences::PreferenceStore;
+use crate::util::extract_second_word;
+
+use itertools::Itertools;
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+
+#[command]
+#[description = "Choose the strict mode new games start in: \
+`off`, `soft`, `nyt` or `strict`. \
+It can still be changed with the buttons before the first guess."]
+pub async fn strict(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let user_id = msg.author.id.0;
+    let available = StrictMode::ALL
+        .iter()
+        .map(|mode| format!("`{}`", mode.name()))
+        .join(", ");
+    let current = preference_store.lock().unwrap().get(user_id);
+    let locale = current.locale;
+
+    let reply = match extract_second_word(&msg.content) {
+        None => BotMessage::CurrentStrictMode {
+            current: current.default_strict_mode,
+            available: &available,
+        }
+        .localize(locale),
+        Some(name) => match StrictMode::from_name(name) {
+            Some(mode) => {
+                preference_store
+                    .lock()
+                    .unwrap()
+                    .update(user_id, |preferences| {
+                        preferences.default_strict_mode = mode
+                    });
+                BotMessage::StrictModeChanged(mode).localize(locale)
+            }
+            None => BotMessage::UnknownStrictMode {
+                name,
+                available: &available,
+            }
+            .localize(locale),
+        },
+    };
+
+    msg.reply(ctx, reply).await?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::i18n::{BotMessage, Localize};
+use crate::model::evaluation::{is_single_emoji, EmojiTheme};
+use crate::preferences::PreferenceStore;
+
+use itertools::Itertools;
+use serenity::client::Context;
+use serenity::framework::standard::{macros::command, CommandResult};
+use serenity::model::channel::Message;
+use serenity::utils::MessageBuilder;
+
+#[command]
+#[description = "Choose the emojis used for the board, keyboard and results: \
+`classic`, `nyt`, `colorblind`, `shapes`, or `custom` followed by three emojis \
+for absent, present and correct letters."]
+pub async fn theme(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+    let preference_store = data.get::<PreferenceStore>().unwrap();
+    let user_id = msg.author.id.0;
+    let available = EmojiTheme::BUILT_IN
+        .iter()
+        .map(|theme| format!("`{}`", theme.name()))
+        .chain(std::iter::once(String::from(
+            "`custom <absent> <present> <correct>`",
+        )))
+        .join(", ");
+    let current = preference_store.lock().unwrap().get(user_id);
+    let locale = current.locale;
+
+    let mut args = msg.content.split_ascii_whitespace().skip(1);
+    let theme = match args.next() {
+        None => {
+            msg.reply(
+                ctx,
+                BotMessage::CurrentTheme {
+                    current: current.theme.name(),
+                    available: &available,
+                }
+                .localize(locale),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(name) if name.eq_ignore_ascii_case("custom") => {
+            match args.collect_tuple::<(&str, &str, &str)>() {
+                Some(emojis) => {
+                    let emojis = <[&str; 3]>::from(emojis);
+                    // Every cell of the board and of shared results shows one of these,
+                    // so anything longer than an emoji would push messages past Discord's length limit.
+                    if let Some(invalid) = emojis.iter().find(|emoji| !is_single_emoji(emoji)) {
+                        let invalid = MessageBuilder::new().push_safe(invalid).build();
+                        msg.reply(ctx, BotMessage::NotSingleEmoji(&invalid).localize(locale))
+                            .await?;
+                        return Ok(());
+                    }
+                    EmojiTheme::Custom(emojis.map(String::from))
+                }
+                None => {
+                    msg.reply(
+                        ctx,
+                        BotMessage::CustomThemeNeedsThreeEmojis.localize(locale),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+        }
+        Some(name) => match EmojiTheme::BUILT_IN
+            .into_iter()
+            .find(|theme| theme.name().eq_ignore_ascii_case(name))
+        {
+            Some(theme) => theme,
+            None => {
+                msg.reply(
+                    ctx,
+                    BotMessage::UnknownTheme {
+                        name,
+                        available: &available,
+                    }
+                    .localize(locale),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let reply = BotMessage::ThemeChanged(theme.name()).localize(locale);
+    preference_store
+        .lock()
+        .unwrap()
+        .update(user_id, |preferences| preferences.theme = theme);
+    msg.reply(ctx, reply).await?;
+    Ok(())
+}
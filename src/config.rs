@@ -0,0 +1,420 @@
+use anyhow::Context;
+use serde::Deserialize;
+use serenity::prelude::TypeMapKey;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use friendle_core::word_list::{DEFAULT_WORD_LIST_PATH, WORD_LIST_PATH_ENV_VAR};
+
+use crate::api::{
+    API_TOKENS_ENV_VAR, DEFAULT_HTTP_HOST, DEFAULT_HTTP_PORT, HTTP_HOST_ENV_VAR, HTTP_PORT_ENV_VAR,
+    MIN_API_TOKEN_LENGTH,
+};
+use crate::logging::{LogFormat, LOG_FORMAT_ENV_VAR, LOG_LEVEL_ENV_VAR};
+use crate::model::game::StrictMode;
+
+pub const CONFIG_PATH_ENV_VAR: &str = "FRIENDLE_CONFIG";
+pub const CONFIG_PATH_FLAG: &str = "--config";
+/// Used if no config file is given explicitly; it's fine for it not to exist.
+pub const DEFAULT_CONFIG_PATH: &str = "friendle.toml";
+
+pub const DISCORD_TOKEN_ENV_VAR: &str = "FRIENDLE_DISCORD_TOKEN";
+pub const DISCORD_APPLICATION_ID_ENV_VAR: &str = "FRIENDLE_APPLICATION_ID";
+pub const PREFERENCES_PATH_ENV_VAR: &str = "PREFERENCES_PATH";
+pub const GAMES_PATH_ENV_VAR: &str = "GAMES_PATH";
+pub const STATS_PATH_ENV_VAR: &str = "STATS_PATH";
+pub const PREFIX_ENV_VAR: &str = "FRIENDLE_PREFIX";
+pub const DAILY_URL_ENV_VAR: &str = "FRIENDLE_DAILY_URL";
+pub const DEFAULT_STRICT_MODE_ENV_VAR: &str = "FRIENDLE_DEFAULT_STRICT_MODE";
+pub const OWNER_IDS_ENV_VAR: &str = "FRIENDLE_OWNER_IDS";
+
+const DEFAULT_DAILY_URL: &str = "https://www.nytimes.com/svc/wordle/v2/{date}.json";
+
+/// Contents of the config file. Everything is optional; environment variables take precedence.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    discord_token: Option<String>,
+    application_id: Option<u64>,
+    prefix: Option<String>,
+    word_list_path: Option<PathBuf>,
+    preferences_path: Option<PathBuf>,
+    games_path: Option<PathBuf>,
+    stats_path: Option<PathBuf>,
+    /// URL of the daily Wordle solution, with `{date}` in place of the date.
+    daily_url: Option<String>,
+    /// Strict mode new games start in for users that haven't picked one.
+    default_strict_mode: Option<String>,
+    /// Address the HTTP API listens on.
+    http_host: Option<String>,
+    http_port: Option<u16>,
+    /// Bearer tokens for the game routes of the HTTP API, each mapped to the Discord user id it plays as.
+    api_tokens: Option<HashMap<String, u64>>,
+    owner_ids: Option<Vec<u64>>,
+    log_level: Option<String>,
+    log_format: Option<String>,
+}
+
+impl ConfigFile {
+    /// Overrides settings with the environment variables that are set.
+    fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) -> Vec<String> {
+        let mut errors = vec![];
+
+        if let Some(token) = env(DISCORD_TOKEN_ENV_VAR) {
+            self.discord_token = Some(token);
+        }
+        if let Some(id) = env(DISCORD_APPLICATION_ID_ENV_VAR) {
+            match parse_env_number(DISCORD_APPLICATION_ID_ENV_VAR, &id) {
+                Ok(id) => self.application_id = Some(id),
+                Err(err) => errors.push(err),
+            }
+        }
+        if let Some(port) = env(HTTP_PORT_ENV_VAR) {
+            match parse_env_number(HTTP_PORT_ENV_VAR, &port) {
+                Ok(port) => self.http_port = Some(port),
+                Err(err) => errors.push(err),
+            }
+        }
+        if let Some(api_tokens) = env(API_TOKENS_ENV_VAR) {
+            let api_tokens = api_tokens
+                .split(',')
+                .filter(|entry| !entry.trim().is_empty())
+                .map(|entry| match entry.split_once('=') {
+                    Some((token, player_id)) => parse_env_number(API_TOKENS_ENV_VAR, player_id)
+                        .map(|player_id| (String::from(token.trim()), player_id)),
+                    None => Err(format!(
+                        "{API_TOKENS_ENV_VAR} must be a comma-separated list of `token=user_id`"
+                    )),
+                })
+                .collect::<Result<_, _>>();
+            match api_tokens {
+                Ok(api_tokens) => self.api_tokens = Some(api_tokens),
+                Err(err) => errors.push(err),
+            }
+        }
+        if let Some(owner_ids) = env(OWNER_IDS_ENV_VAR) {
+            let owner_ids = owner_ids
+                .split(',')
+                .filter(|id| !id.trim().is_empty())
+                .map(|id| parse_env_number(OWNER_IDS_ENV_VAR, id))
+                .collect::<Result<_, _>>();
+            match owner_ids {
+                Ok(owner_ids) => self.owner_ids = Some(owner_ids),
+                Err(err) => errors.push(err),
+            }
+        }
+        for (var, setting) in [
+            (PREFIX_ENV_VAR, &mut self.prefix),
+            (HTTP_HOST_ENV_VAR, &mut self.http_host),
+            (DAILY_URL_ENV_VAR, &mut self.daily_url),
+            (DEFAULT_STRICT_MODE_ENV_VAR, &mut self.default_strict_mode),
+            (LOG_LEVEL_ENV_VAR, &mut self.log_level),
+            (LOG_FORMAT_ENV_VAR, &mut self.log_format),
+        ] {
+            if let Some(value) = env(var) {
+                *setting = Some(value);
+            }
+        }
+        for (var, setting) in [
+            (WORD_LIST_PATH_ENV_VAR, &mut self.word_list_path),
+            (PREFERENCES_PATH_ENV_VAR, &mut self.preferences_path),
+            (GAMES_PATH_ENV_VAR, &mut self.games_path),
+            (STATS_PATH_ENV_VAR, &mut self.stats_path),
+        ] {
+            if let Some(value) = env(var) {
+                *setting = Some(PathBuf::from(value));
+            }
+        }
+        errors
+    }
+}
+
+fn parse_env_number<T: std::str::FromStr>(var: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{var} must be a valid number, got `{value}`"))
+}
+
+/// Validated bot configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub discord_token: String,
+    /// Usually the bot user id; needed for components.
+    pub application_id: u64,
+    pub prefix: String,
+    pub word_list_path: PathBuf,
+    pub preferences_path: PathBuf,
+    /// Games in progress are saved here on shutdown and restored on startup.
+    pub games_path: PathBuf,
+    /// Statistics are saved here on shutdown and restored on startup.
+    pub stats_path: PathBuf,
+    pub daily_url: String,
+    pub default_strict_mode: StrictMode,
+    pub http_host: IpAddr,
+    pub http_port: u16,
+    /// Discord user id per API token; the game routes of the HTTP API reject requests without one.
+    pub api_tokens: HashMap<String, u64>,
+    pub owner_ids: HashSet<u64>,
+    pub log_level: String,
+    pub log_format: LogFormat,
+}
+
+impl TypeMapKey for Config {
+    type Value = Arc<Config>;
+}
+
+impl Config {
+    /// Loads the config file given by `--config <path>` or the config path environment variable,
+    /// falling back to `friendle.toml` if it exists, and applies environment overrides.
+    pub fn load(args: &[String]) -> anyhow::Result<Self> {
+        let explicit_path = match args.iter().position(|arg| arg == CONFIG_PATH_FLAG) {
+            Some(index) => Some(
+                args.get(index + 1)
+                    .with_context(|| format!("{CONFIG_PATH_FLAG} requires a path"))?
+                    .into(),
+            ),
+            None => std::env::var(CONFIG_PATH_ENV_VAR).ok().map(PathBuf::from),
+        };
+        let file = match explicit_path {
+            Some(path) => Self::read_file(&path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                Self::read_file(Path::new(DEFAULT_CONFIG_PATH))?
+            }
+            None => ConfigFile::default(),
+        };
+        Self::from_file(file, |var| std::env::var(var).ok())
+    }
+
+    fn read_file(path: &Path) -> anyhow::Result<ConfigFile> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&text)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    /// Applies the environment overrides and checks the result, reporting all problems at once.
+    fn from_file(
+        mut file: ConfigFile,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut errors = file.apply_env(env);
+
+        let discord_token = file.discord_token.unwrap_or_default();
+        if discord_token.trim().is_empty() {
+            errors.push(format!(
+                "No Discord token; set `discord_token` or {DISCORD_TOKEN_ENV_VAR}"
+            ));
+        }
+        let application_id = file.application_id.unwrap_or_default();
+        if application_id == 0 {
+            errors.push(format!(
+                "No application id; set `application_id` or {DISCORD_APPLICATION_ID_ENV_VAR}"
+            ));
+        }
+        let prefix = file.prefix.unwrap_or_else(|| String::from("."));
+        if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+            errors.push(format!(
+                "The prefix must not be empty or contain spaces, got `{prefix}`"
+            ));
+        }
+        let word_list_path = file
+            .word_list_path
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_WORD_LIST_PATH));
+        if !word_list_path.is_file() {
+            errors.push(format!(
+                "Word list {} does not exist",
+                word_list_path.display()
+            ));
+        }
+        let daily_url = file
+            .daily_url
+            .unwrap_or_else(|| String::from(DEFAULT_DAILY_URL));
+        if !daily_url.contains("{date}") {
+            errors.push(format!(
+                "The daily URL must contain `{{date}}`, got `{daily_url}`"
+            ));
+        }
+        let default_strict_mode = match file.default_strict_mode {
+            None => StrictMode::default(),
+            Some(name) => match StrictMode::ALL.into_iter().find(|mode| mode.name() == name) {
+                Some(mode) => mode,
+                None => {
+                    let names = StrictMode::ALL.map(|mode| mode.name()).join(", ");
+                    errors.push(format!(
+                        "Unknown default strict mode `{name}`, expected one of {names}"
+                    ));
+                    StrictMode::default()
+                }
+            },
+        };
+        let http_host = match file.http_host {
+            None => DEFAULT_HTTP_HOST,
+            Some(host) => host.parse().unwrap_or_else(|_| {
+                errors.push(format!("The HTTP host must be an IP address, got `{host}`"));
+                DEFAULT_HTTP_HOST
+            }),
+        };
+        let http_port = file.http_port.unwrap_or(DEFAULT_HTTP_PORT);
+        if http_port == 0 {
+            errors.push(String::from("The HTTP port must not be 0"));
+        }
+        let api_tokens = file.api_tokens.unwrap_or_default();
+        if api_tokens
+            .keys()
+            .any(|token| token.len() < MIN_API_TOKEN_LENGTH)
+        {
+            errors.push(format!(
+                "API tokens must be at least {MIN_API_TOKEN_LENGTH} characters long"
+            ));
+        }
+        let log_format = match file.log_format {
+            None => LogFormat::Pretty,
+            Some(format) => format.parse().unwrap_or_else(|err| {
+                errors.push(err);
+                LogFormat::Pretty
+            }),
+        };
+
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid configuration:\n- {}", errors.join("\n- "));
+        }
+        Ok(Self {
+            discord_token,
+            application_id,
+            prefix,
+            word_list_path,
+            preferences_path: file
+                .preferences_path
+                .unwrap_or_else(|| PathBuf::from("preferences.json")),
+            games_path: file
+                .games_path
+                .unwrap_or_else(|| PathBuf::from("games.json")),
+            stats_path: file
+                .stats_path
+                .unwrap_or_else(|| PathBuf::from("stats.json")),
+            daily_url,
+            default_strict_mode,
+            http_host,
+            http_port,
+            api_tokens,
+            owner_ids: file.owner_ids.unwrap_or_default().into_iter().collect(),
+            log_level: file.log_level.unwrap_or_else(|| String::from("info")),
+            log_format,
+        })
+    }
+
+    /// How users invoke the command with the given name, e.g. `.play`.
+    pub fn command(&self, name: &str) -> String {
+        format!("{}{name}", self.prefix)
+    }
+
+    /// The URL to fetch the daily Wordle solution for the given date from.
+    pub fn daily_url(&self, date: &str) -> String {
+        self.daily_url.replace("{date}", date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_config_file_with_env_overrides() {
+        let file: ConfigFile = toml::from_str(
+            r#"
+            discord_token = "file-token"
+            application_id = 42
+            prefix = "!"
+            word_list_path = "resources/wordlist.txt"
+            default_strict_mode = "nyt"
+            owner_ids = [1, 2]
+
+            [api_tokens]
+            "file-token-0123456789" = 1
+            "#,
+        )
+        .unwrap();
+        let config = Config::from_file(
+            file,
+            env(&[
+                (DISCORD_TOKEN_ENV_VAR, "env-token"),
+                (HTTP_PORT_ENV_VAR, "9090"),
+                (
+                    API_TOKENS_ENV_VAR,
+                    "env-token-0123456789=2, other-token-0123456789=3",
+                ),
+                (LOG_FORMAT_ENV_VAR, "json"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.discord_token, "env-token");
+        assert_eq!(config.application_id, 42);
+        assert_eq!(config.prefix, "!");
+        assert_eq!(config.command("play"), "!play");
+        assert_eq!(config.default_strict_mode, StrictMode::NytHard);
+        assert_eq!(config.owner_ids, HashSet::from([1, 2]));
+        assert_eq!(config.http_host, DEFAULT_HTTP_HOST);
+        assert_eq!(config.http_port, 9090);
+        assert_eq!(
+            config.api_tokens,
+            HashMap::from([
+                (String::from("env-token-0123456789"), 2),
+                (String::from("other-token-0123456789"), 3),
+            ])
+        );
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.preferences_path, PathBuf::from("preferences.json"));
+        assert_eq!(config.games_path, PathBuf::from("games.json"));
+        assert_eq!(
+            config.daily_url("2022-06-01"),
+            "https://www.nytimes.com/svc/wordle/v2/2022-06-01.json"
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_reports_all_errors() {
+        assert!(toml::from_str::<ConfigFile>("unknown_setting = 1").is_err());
+
+        let file: ConfigFile = toml::from_str(
+            r#"
+            prefix = ""
+            word_list_path = "does/not/exist.txt"
+            daily_url = "https://example.com/daily.json"
+            default_strict_mode = "hard"
+            http_host = "localhost"
+
+            [api_tokens]
+            short = 1
+            "#,
+        )
+        .unwrap();
+        let err = Config::from_file(file, env(&[(HTTP_PORT_ENV_VAR, "eighty")]))
+            .unwrap_err()
+            .to_string();
+        for expected in [
+            "HTTP_PORT must be a valid number",
+            "No Discord token",
+            "No application id",
+            "prefix must not be empty",
+            "does/not/exist.txt does not exist",
+            "must contain `{date}`",
+            "Unknown default strict mode `hard`",
+            "HTTP host must be an IP address",
+            "API tokens must be at least 16 characters",
+        ] {
+            assert!(err.contains(expected), "{expected} missing in {err}");
+        }
+    }
+}
@@ -1,26 +1,49 @@
 use std::str::FromStr;
 
 use serenity::async_trait;
-use serenity::client::{Context, EventHandler};
+use serenity::client::bridge::gateway::event::ShardStageUpdateEvent;
+use serenity::client::{Context, EventHandler, RawEventHandler};
+use serenity::gateway::ConnectionStage;
+use serenity::model::event::Event;
 
 use serenity::model::gateway::Ready;
 use serenity::model::interactions::message_component::ComponentType;
 
 use serenity::model::interactions::Interaction;
 
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
 use crate::buttons::FriendleButton;
+use crate::commands::settings::SettingsMenu;
+use crate::metrics::GatewayStatus;
+use crate::shutdown::Shutdown;
 
 pub struct Handler;
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, _: Context, ready: Ready) {
-        println!("{} is connected!", ready.user.name);
+        info!("{} is connected!", ready.user.name);
+    }
+
+    async fn shard_stage_update(&self, ctx: Context, event: ShardStageUpdateEvent) {
+        let data = ctx.data.read().await;
+        data.get::<GatewayStatus>()
+            .unwrap()
+            .set_connected(event.new == ConnectionStage::Connected);
     }
 
     // This could be handled via Serenity's `collector` feature in theory,
     // but for some reason, callbacks were never actually called when I tested that.
     async fn interaction_create(&self, ctx: Context, mut interaction: Interaction) {
+        let shutdown = ctx.data.read().await.get::<Shutdown>().unwrap().clone();
+        let _guard = match shutdown.start_handler() {
+            Some(guard) => guard,
+            None => {
+                debug!(kind = ?interaction.kind(), "Ignoring interaction during shutdown");
+                return;
+            }
+        };
         match interaction {
             Interaction::MessageComponent(ref mut mci)
                 if mci.data.component_type == ComponentType::Button =>
@@ -32,14 +55,54 @@ impl EventHandler for Handler {
                     }
 
                     Err(err) => {
-                        eprintln!("button interaction {mci:?} led to error {err}");
+                        warn!(
+                            user_id = mci.user.id.0,
+                            "Button interaction led to error: {err}"
+                        );
                         return;
                     }
                 }
             }
+            Interaction::MessageComponent(ref mut mci)
+                if mci.data.component_type == ComponentType::SelectMenu =>
+            {
+                match SettingsMenu::from_str(&mci.data.custom_id) {
+                    Ok(menu) => {
+                        let span = info_span!(
+                            "select_menu_interaction",
+                            user_id = mci.user.id.0,
+                            menu = menu.id(),
+                        );
+                        if let Err(e) = menu.handle_interaction(&ctx, mci).instrument(span).await {
+                            error!(
+                                user_id = mci.user.id.0,
+                                menu = menu.id(),
+                                "Error during select menu interaction: {e}"
+                            );
+                        }
+                    }
+                    Err(_) => {
+                        warn!(
+                            custom_id = %mci.data.custom_id,
+                            "Unknown select menu in interaction"
+                        );
+                    }
+                }
+            }
             _ => {
-                println!("non-button interaction: {interaction:?}");
+                debug!(kind = ?interaction.kind(), "Ignoring non-component interaction");
             }
         }
     }
 }
+
+/// Records the time of every gateway event for `/healthz`.
+pub struct GatewayEventRecorder;
+
+#[async_trait]
+impl RawEventHandler for GatewayEventRecorder {
+    async fn raw_event(&self, ctx: Context, _: Event) {
+        let data = ctx.data.read().await;
+        data.get::<GatewayStatus>().unwrap().record_event();
+    }
+}
@@ -1,18 +1,27 @@
+use crate::board_image::render_board;
 use crate::buttons::copy_result_button::CopyResultButton;
 
 use crate::buttons::mode_button::ModeButton;
-use crate::buttons::show_keyboard_button::ShowKeyboardButton;
-use crate::model::evaluation::EmojiMode;
-use crate::model::game::{GameState, StrictMode};
-use crate::player::PlayerState;
+use crate::buttons::FriendleButton;
+use crate::config::Config;
+use crate::game_store::GameStore;
+use crate::i18n::{BotMessage, Localize};
+use crate::metrics::Metrics;
+use crate::model::game::GameState;
+use crate::model::guess_error::{GuessError, InvalidWordError};
+use crate::preferences::{DisplayMode, PreferenceStore};
+use crate::render::DiscordRenderer;
+use crate::stats::CodeStatsStore;
+use crate::transport::{DiscordTransport, IncomingMessage, OutgoingMessage, Transport};
 
 use crate::wordlist::WordList;
 
 use serenity::framework::standard::macros::hook;
 use serenity::model::channel::Message;
 
-use serenity::prelude::Context;
+use serenity::prelude::{Context, TypeMap};
 use serenity::utils::MessageBuilder;
+use tracing::{error, field, info_span, warn, Instrument, Span};
 
 #[hook]
 pub async fn message_hook(ctx: &Context, msg: &Message) {
@@ -20,49 +29,105 @@ pub async fn message_hook(ctx: &Context, msg: &Message) {
     // await the next response in the channel where the game was started
     // instead of using a message hook; however, when I tested it,
     // callbacks were never actually executed that way.
-    if let Err(err) = handle_message(ctx, msg).await {
-        eprintln!("Encountered error in game loop: {err}");
+    let span = info_span!("guess", code = field::Empty, game_state = field::Empty);
+    let data = ctx.data.read().await;
+    let transport = DiscordTransport::from(ctx);
+    if let Err(err) = handle_message(&data, &transport, &IncomingMessage::from(msg))
+        .instrument(span)
+        .await
+    {
+        error!("Encountered error in game loop: {err}");
     }
 }
 
-async fn handle_message(ctx: &Context, msg: &Message) -> anyhow::Result<()> {
-    let data = ctx.data.read().await;
-
-    if msg.content.starts_with('.') {
+pub async fn handle_message(
+    data: &TypeMap,
+    transport: &dyn Transport,
+    msg: &IncomingMessage,
+) -> anyhow::Result<()> {
+    if msg
+        .content
+        .starts_with(data.get::<Config>().unwrap().prefix.as_str())
+    {
         // ignore commands
         return Ok(());
     }
 
-    if msg.guild_id.is_some() {
+    if msg.in_guild {
+        let locale = {
+            let preference_store = data.get::<PreferenceStore>().unwrap();
+            let lock = preference_store.lock().unwrap();
+            lock.get(msg.author_id).locale
+        };
         // ideally, this reply should be ephemeral, but ephemeral messages are restricted to interaction responses
-        msg.reply(ctx, "Guesses are only accepted in DMs").await?;
+        transport
+            .reply(msg, BotMessage::GuessesOnlyInDms.localize(locale))
+            .await?;
         return Ok(());
     }
 
-    let user = &msg.author;
+    let preferences = {
+        let preference_store = data.get::<PreferenceStore>().unwrap();
+        let lock = preference_store.lock().unwrap();
+        lock.get(msg.author_id)
+    };
+    let locale = preferences.locale;
 
     let word_list = data.get::<WordList>().unwrap();
-    let player_state = data.get::<PlayerState>().unwrap();
-
-    let game = {
-        let mut lock = player_state.lock().unwrap();
-        lock.games_per_player.get_mut(&user.id.0).cloned()
+    // Held until the guess has been answered, so button clicks for this game wait for it.
+    let mut game = match data.get::<GameStore>().unwrap().lock(msg.author_id).await {
+        Some(game) => game,
+        None => {
+            transport
+                .reply(msg, BotMessage::NoGameInProgress.localize(locale))
+                .await?;
+            return Ok(());
+        }
     };
 
-    if game.is_none() {
-        msg.reply(ctx, "No game in progress.").await?;
+    let guess = msg.content.to_ascii_lowercase();
+    Span::current().record("code", game.code().value);
+
+    if let Err(err) = game.guess(guess.clone(), &word_list.words) {
+        data.get::<Metrics>().unwrap().guess_rejected(&err);
+        let mut reply = err.localize(locale);
+        if let GuessError::InvalidWord(
+            InvalidWordError::NotInWordList { .. } | InvalidWordError::WrongLength { .. },
+        ) = err
+        {
+            let suggestions =
+                game.suggestions(&guess, &word_list.words, preferences.keyboard_layout);
+            if !suggestions.is_empty() {
+                reply += "\n";
+                reply += &BotMessage::DidYouMean(&suggestions).localize(locale);
+            }
+        }
+        transport.reply(msg, reply).await?;
         return Ok(());
     }
 
-    let mut game = game.unwrap();
-    let guess = msg.content.to_ascii_lowercase();
-
-    if let Err(err) = game.guess(guess, &word_list.words) {
-        msg.reply(ctx, format!("{err}")).await?;
-        return Ok(());
+    if let Some(warning) = game
+        .history()
+        .last()
+        .and_then(|guess| guess.warning.as_ref())
+    {
+        transport
+            .reply(msg, BotMessage::WastedGuess(warning).localize(locale))
+            .await?;
     }
 
     let game_state = game.state();
+    Span::current().record("game_state", field::debug(game_state));
+    if game_state != GameState::InProgress {
+        let stats = data.get::<CodeStatsStore>().unwrap();
+        stats
+            .lock()
+            .unwrap()
+            .record_result(game.code().value, game_state, game.history().len());
+        data.get::<Metrics>()
+            .unwrap()
+            .game_finished(game_state, game.get_strict_mode());
+    }
 
     let mut message_builder = MessageBuilder::new();
 
@@ -71,15 +136,14 @@ async fn handle_message(ctx: &Context, msg: &Message) -> anyhow::Result<()> {
         GameState::Lost => {
             let solution = game.solution();
             // TODO add extra loss messages and select one at random for fun
-            msg.reply(
-                &ctx,
-                format!("Unfortunately, you're out of tries. The solution was ||`{solution}`||"),
-            )
-            .await?;
+            transport
+                .reply(msg, BotMessage::Lost { solution }.localize(locale))
+                .await?;
         }
         GameState::Won => {
             // TODO add extra win messages and select one at random for fun
-            msg.reply(&ctx, String::from("You won! Good job :)"))
+            transport
+                .reply(msg, BotMessage::Won.localize(locale))
                 .await?;
         }
         GameState::InProgress => {
@@ -87,51 +151,62 @@ async fn handle_message(ctx: &Context, msg: &Message) -> anyhow::Result<()> {
         }
     }
 
-    game.display_game_state_header(&mut message_builder);
-    game.display_state(&mut message_builder, EmojiMode::Unicode);
-
-    msg.channel_id
-        .send_message(&ctx, |m| {
-            m.content(message_builder);
-            match game_state {
-                GameState::InProgress => {
-                    m.components(|comps| {
-                        comps.create_action_row(|row| {
-                            row.add_button(ShowKeyboardButton::button());
-                            let target_mode =
-                                if game.can_switch_to_mode(StrictMode::Disabled).is_ok() {
-                                    Some(StrictMode::Disabled)
-                                } else if game.can_switch_to_mode(StrictMode::Enabled).is_ok() {
-                                    Some(StrictMode::Enabled)
-                                } else {
-                                    None
-                                };
-                            if let Some(mode) = target_mode {
-                                row.add_button(ModeButton::new(mode).mode_button());
-                            }
-                            row
-                        });
-                        comps
-                    });
-                }
-                _ => {
-                    m.components(|comps| {
-                        comps.create_action_row(|row| row.add_button(CopyResultButton::button()));
-                        comps
-                    });
-                }
+    game.display_game_state_header(&mut DiscordRenderer(&mut message_builder), locale);
+    let board_image = match preferences.display_mode {
+        DisplayMode::Image => match render_board(&game, &preferences.theme) {
+            Ok(png) => Some(png),
+            Err(e) => {
+                warn!("Failed to render board image, falling back to text: {e}");
+                None
             }
-            m
-        })
-        .await?;
-
-    {
-        // Get the game state again and update it.
-        // Always keep the game state until a new game is started, even if it's already finished.
-        // This allows us to run button commands to copy the game in a format a user can copy paste to share their results.
-        let mut lock = player_state.lock().unwrap();
-        lock.games_per_player.insert(user.id.0, game);
+        },
+        DisplayMode::Text | DisplayMode::ScreenReader => None,
+    };
+    if preferences.display_mode == DisplayMode::ScreenReader {
+        game.display_state_as_text(&mut DiscordRenderer(&mut message_builder), locale);
+    } else if board_image.is_none() {
+        game.display_state(
+            &mut DiscordRenderer(&mut message_builder),
+            preferences.board_emoji_mode,
+            &preferences.theme,
+        );
     }
 
+    let buttons = match game_state {
+        GameState::InProgress => {
+            let mut rows = vec![vec![
+                FriendleButton::ShowKeyboard,
+                FriendleButton::ShowKnowledge,
+            ]];
+            let mode_switch_options = game.mode_switch_options();
+            if !mode_switch_options.is_empty() {
+                rows.push(
+                    mode_switch_options
+                        .into_iter()
+                        .map(|mode| FriendleButton::ModeChangeButton(ModeButton::new(mode)))
+                        .collect(),
+                );
+            }
+            rows
+        }
+        _ => vec![CopyResultButton::ALL
+            .into_iter()
+            .map(FriendleButton::CopyResultButton)
+            .collect()],
+    };
+    transport
+        .send_message(
+            msg.channel_id,
+            OutgoingMessage {
+                content: message_builder.build(),
+                image: board_image,
+                buttons,
+                locale,
+            },
+        )
+        .await?;
+
+    // The game is kept until a new game is started, even if it's already finished.
+    // This allows us to run button commands to copy the game in a format a user can copy paste to share their results.
     Ok(())
 }
@@ -0,0 +1,259 @@
+use serenity::prelude::TypeMapKey;
+use std::collections::hash_map::Entry;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::model::game::{Game, GameState};
+use crate::storage::{load_json, save_json};
+
+/// The game of every player, each behind its own lock.
+///
+/// Handlers lock a player's game for their whole load, mutate, save cycle, so concurrent guesses
+/// and button clicks of the same player can't overwrite each other, while other players aren't blocked.
+#[derive(Debug, Default)]
+pub struct GameStore {
+    /// Only locked to look up or insert a game, never across an `await`.
+    games_per_player: Mutex<HashMap<u64, Arc<AsyncMutex<Game>>>>,
+    /// File the games are saved to on shutdown; not persisted if unset.
+    path: Option<PathBuf>,
+    /// Number of games in progress, kept up to date by the guards so it can be read without locking any game.
+    in_progress: Arc<AtomicUsize>,
+}
+
+/// A locked game; updates the store's count of games in progress when dropped.
+#[derive(Debug)]
+pub struct GameGuard {
+    game: OwnedMutexGuard<Game>,
+    was_in_progress: bool,
+    in_progress: Arc<AtomicUsize>,
+}
+
+impl GameGuard {
+    fn new(game: OwnedMutexGuard<Game>, in_progress: Arc<AtomicUsize>) -> Self {
+        Self {
+            was_in_progress: game.state() == GameState::InProgress,
+            game,
+            in_progress,
+        }
+    }
+}
+
+impl Deref for GameGuard {
+    type Target = Game;
+
+    fn deref(&self) -> &Game {
+        &self.game
+    }
+}
+
+impl DerefMut for GameGuard {
+    fn deref_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+}
+
+impl Drop for GameGuard {
+    fn drop(&mut self) {
+        let is_in_progress = self.game.state() == GameState::InProgress;
+        if self.was_in_progress && !is_in_progress {
+            self.in_progress.fetch_sub(1, Ordering::Relaxed);
+        } else if !self.was_in_progress && is_in_progress {
+            self.in_progress.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl TypeMapKey for GameStore {
+    type Value = Arc<GameStore>;
+}
+
+impl GameStore {
+    /// Loads the games saved at the given path, starting out empty if the file doesn't exist yet.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let games: HashMap<u64, Game> = load_json(&path)?;
+        let in_progress = games
+            .values()
+            .filter(|game| game.state() == GameState::InProgress)
+            .count();
+        Ok(Self {
+            games_per_player: Mutex::new(
+                games
+                    .into_iter()
+                    .map(|(player_id, game)| (player_id, Arc::new(AsyncMutex::new(game))))
+                    .collect(),
+            ),
+            path: Some(path),
+            in_progress: Arc::new(AtomicUsize::new(in_progress)),
+        })
+    }
+
+    pub async fn save(&self) -> anyhow::Result<()> {
+        match &self.path {
+            Some(path) => save_json(path, &self.snapshot().await),
+            None => Ok(()),
+        }
+    }
+
+    /// Number of players with a game, finished or not.
+    pub fn len(&self) -> usize {
+        self.games_per_player.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn handle(&self, player_id: u64) -> Option<Arc<AsyncMutex<Game>>> {
+        self.games_per_player
+            .lock()
+            .unwrap()
+            .get(&player_id)
+            .cloned()
+    }
+
+    /// Locks the player's game until the guard is dropped; changes made through the guard are saved in place.
+    /// Waits for other handlers working on the same game.
+    pub async fn lock(&self, player_id: u64) -> Option<GameGuard> {
+        let game = self.handle(player_id)?.lock_owned().await;
+        Some(GameGuard::new(game, self.in_progress.clone()))
+    }
+
+    /// A copy of the player's game, for handlers that only read it.
+    pub async fn get(&self, player_id: u64) -> Option<Game> {
+        Some(self.lock(player_id).await?.clone())
+    }
+
+    /// Starts a new game for the player unless they have one in progress.
+    /// Finished games are kept until then so results can still be shared.
+    /// Returns whether the game was started.
+    pub async fn start(&self, player_id: u64, game: Game) -> bool {
+        let handle = match self.games_per_player.lock().unwrap().entry(player_id) {
+            Entry::Vacant(entry) => {
+                if game.state() == GameState::InProgress {
+                    self.in_progress.fetch_add(1, Ordering::Relaxed);
+                }
+                entry.insert(Arc::new(AsyncMutex::new(game)));
+                return true;
+            }
+            Entry::Occupied(entry) => entry.get().clone(),
+        };
+        let mut current = GameGuard::new(handle.lock_owned().await, self.in_progress.clone());
+        if current.state() == GameState::InProgress {
+            return false;
+        }
+        *current = game;
+        true
+    }
+
+    /// Copies of all games, waiting for handlers that are still working on one.
+    pub async fn snapshot(&self) -> HashMap<u64, Game> {
+        let handles = self
+            .games_per_player
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(player_id, handle)| (*player_id, handle.clone()))
+            .collect::<Vec<_>>();
+        let mut games = HashMap::with_capacity(handles.len());
+        for (player_id, handle) in handles {
+            games.insert(player_id, handle.lock().await.clone());
+        }
+        games
+    }
+
+    /// Number of games in progress, without waiting for handlers that are working on a game.
+    pub fn count_in_progress(&self) -> usize {
+        self.in_progress.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::coding::Code;
+    use crate::model::game::StrictMode;
+    use std::collections::HashSet;
+
+    fn word_list() -> HashSet<String> {
+        HashSet::from_iter(["tales", "lanes"].map(String::from))
+    }
+
+    fn new_game(solution: &str) -> Game {
+        Game::new(Code { value: 1 }, String::from(solution), &word_list()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_updates_are_not_lost() {
+        let store = Arc::new(GameStore::default());
+        assert!(store.start(1, new_game("tales")).await);
+        assert!(!store.start(1, new_game("lanes")).await);
+
+        // a guess holds the lock while it replies, and a mode change has to wait for it
+        let mut guess = store.lock(1).await.unwrap();
+        let mode_change = tokio::spawn({
+            let store = store.clone();
+            async move {
+                let mut game = store.lock(1).await.unwrap();
+                game.set_strict_mode(StrictMode::Warn)
+            }
+        });
+        guess.guess(String::from("lanes"), &word_list()).unwrap();
+        tokio::task::yield_now().await;
+        drop(guess);
+        assert!(mode_change.await.unwrap().is_ok());
+
+        let game = store.get(1).await.unwrap();
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(game.get_strict_mode(), StrictMode::Warn);
+        assert_eq!(store.count_in_progress(), 1);
+
+        // finished games are replaced by new ones
+        store
+            .lock(1)
+            .await
+            .unwrap()
+            .guess(String::from("tales"), &word_list())
+            .unwrap();
+        assert_eq!(store.count_in_progress(), 0);
+        assert!(store.start(1, new_game("lanes")).await);
+        assert_eq!(store.get(1).await.unwrap().solution(), "lanes");
+        assert_eq!(store.count_in_progress(), 1);
+
+        // the count doesn't wait for a handler holding a game
+        assert!(store.start(2, new_game("tales")).await);
+        let _busy = store.lock(2).await.unwrap();
+        assert_eq!(store.count_in_progress(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_games_are_persisted() {
+        let path = std::env::temp_dir().join(format!("friendle-games-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = GameStore::load(path.clone()).unwrap();
+        assert!(store.is_empty());
+        assert!(store.start(1, new_game("tales")).await);
+        store
+            .lock(1)
+            .await
+            .unwrap()
+            .guess(String::from("lanes"), &word_list())
+            .unwrap();
+        store.save().await.unwrap();
+
+        let loaded = GameStore::load(path.clone()).unwrap();
+        assert_eq!(loaded.count_in_progress(), 1);
+        let mut game = loaded.lock(1).await.unwrap();
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(game.history()[0].word, "lanes");
+        game.guess(String::from("tales"), &word_list()).unwrap();
+        assert_eq!(game.state(), GameState::Won);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,492 @@
+pub use friendle_core::i18n::{GameMessage, Locale, Localize};
+
+use crate::buttons::copy_result_button::ShareFormat;
+use crate::commands::settings::SettingsMenu;
+use crate::model::difficulty::Difficulty;
+use crate::model::game::{LetterState, StrictMode};
+use crate::model::knowledge::KnowledgeValidationError;
+use crate::preferences::{DisplayMode, UserPreferences};
+use friendle_core::keyboard::KeyboardLayout;
+
+/// Catalog of user-facing bot messages.
+#[derive(Debug, Clone, Copy)]
+pub enum BotMessage<'a> {
+    GuessesOnlyInDms,
+    NoGameInProgress,
+    GameAlreadyInProgress,
+    InternalError,
+    StartGuessing,
+    EstimatedDifficulty(Difficulty),
+    SolutionNotInWordList,
+    DefaultStrictMode(StrictMode),
+    DidYouMean(&'a [String]),
+    WastedGuess(&'a KnowledgeValidationError),
+    Won,
+    Lost {
+        solution: &'a str,
+    },
+    GameNotFinished,
+    ModeChanged(StrictMode),
+    ModeButtonLabel(StrictMode),
+    ShowKeyboardLabel,
+    ShowKnowledgeLabel,
+    CopyResultLabel(ShareFormat),
+    CurrentLocale(Locale),
+    LocaleChanged(Locale),
+    UnknownLocale(&'a str),
+    Help {
+        prefix: &'a str,
+    },
+    KnowledgePattern(&'a str),
+    NotInPosition {
+        position: usize,
+        letters: &'a str,
+    },
+    RequiredLetters(&'a str),
+    AbsentLetters(&'a str),
+    KeyboardLegend {
+        correct: &'a str,
+        present: &'a str,
+        absent: &'a str,
+    },
+    KeyboardGroup(LetterState),
+    SettingsSummary {
+        preferences: &'a UserPreferences,
+        prefix: &'a str,
+    },
+    SettingsPlaceholder(SettingsMenu),
+    CurrentLayout {
+        current: KeyboardLayout,
+        available: &'a str,
+    },
+    LayoutChanged(KeyboardLayout),
+    UnknownLayout {
+        name: &'a str,
+        available: &'a str,
+    },
+    CurrentTheme {
+        current: &'a str,
+        available: &'a str,
+    },
+    ThemeChanged(&'a str),
+    UnknownTheme {
+        name: &'a str,
+        available: &'a str,
+    },
+    NotSingleEmoji(&'a str),
+    CustomThemeNeedsThreeEmojis,
+    CurrentDisplayMode {
+        current: DisplayMode,
+        available: &'a str,
+    },
+    DisplayModeChanged(DisplayMode),
+    UnknownDisplayMode {
+        name: &'a str,
+        available: &'a str,
+    },
+    CurrentStrictMode {
+        current: StrictMode,
+        available: &'a str,
+    },
+    StrictModeChanged(StrictMode),
+    UnknownStrictMode {
+        name: &'a str,
+        available: &'a str,
+    },
+    UnknownRandomOption(&'a str),
+    NoMatchingWord,
+    ShareRandomWord,
+    MissingCode,
+    InvalidCode(&'a str),
+    CodeDifficulty {
+        code: u32,
+        difficulty: Difficulty,
+    },
+    NobodyPlayedYet,
+    CodeStats {
+        started: u32,
+        won: u32,
+        lost: u32,
+    },
+    MissingWord,
+    InvalidWordFormat(&'a str),
+    ShareEncodedWord,
+    WordNotInWordList,
+    DailyUnavailable(&'a str),
+}
+
+impl Localize for BotMessage<'_> {
+    fn localize(&self, locale: Locale) -> String {
+        match *self {
+            BotMessage::GuessesOnlyInDms => String::from(locale.pick(
+                "Guesses are only accepted in DMs",
+                "Rateversuche werden nur in Direktnachrichten angenommen",
+            )),
+            BotMessage::NoGameInProgress => String::from(locale.pick(
+                "No game in progress.",
+                "Es läuft gerade kein Spiel.",
+            )),
+            BotMessage::GameAlreadyInProgress => String::from(locale.pick(
+                "Game aleady in progress!",
+                "Es läuft bereits ein Spiel!",
+            )),
+            BotMessage::InternalError => String::from(locale.pick(
+                "Encountered an internal error.",
+                "Es ist ein interner Fehler aufgetreten.",
+            )),
+            BotMessage::StartGuessing => String::from(locale.pick(
+                "You can now start guessing. Good luck.",
+                "Du kannst jetzt mit dem Raten beginnen. Viel Glück.",
+            )),
+            BotMessage::EstimatedDifficulty(difficulty) => {
+                let difficulty = difficulty.localize(locale);
+                locale.pick(
+                    format!("Estimated difficulty: {difficulty}."),
+                    format!("Geschätzte Schwierigkeit: {difficulty}."),
+                )
+            }
+            BotMessage::SolutionNotInWordList => String::from(locale.pick(
+                "Btw, the solution to this one is not in the original word list.",
+                "Übrigens: Die Lösung steht nicht in der ursprünglichen Wortliste.",
+            )),
+            BotMessage::DefaultStrictMode(mode) => locale.pick(
+                format!("This game uses your default mode `{}`.", mode.name()),
+                format!("Dieses Spiel verwendet deinen Standardmodus `{}`.", mode.name()),
+            ),
+            BotMessage::DidYouMean(suggestions) => {
+                let suggestions = suggestions
+                    .iter()
+                    .map(|word| format!("`{word}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                locale.pick(
+                    format!("Did you mean {suggestions}?"),
+                    format!("Meintest du {suggestions}?"),
+                )
+            }
+            BotMessage::WastedGuess(warning) => {
+                let warning = warning.localize(locale);
+                locale.pick(
+                    format!("⚠️ {warning} This guess counts as wasted."),
+                    format!("⚠️ {warning} Dieser Versuch zählt als verschwendet."),
+                )
+            }
+            BotMessage::Won => String::from(locale.pick(
+                "You won! Good job :)",
+                "Gewonnen! Gut gemacht :)",
+            )),
+            BotMessage::Lost { solution } => locale.pick(
+                format!("Unfortunately, you're out of tries. The solution was ||`{solution}`||"),
+                format!("Leider hast du keine Versuche mehr. Die Lösung war ||`{solution}`||"),
+            ),
+            BotMessage::GameNotFinished => String::from(locale.pick(
+                "The current game isn't finished yet.",
+                "Das aktuelle Spiel ist noch nicht beendet.",
+            )),
+            BotMessage::ModeChanged(mode) => String::from(match mode {
+                StrictMode::Disabled => locale.pick(
+                    "Disabled strict mode.",
+                    "Strikter Modus deaktiviert.",
+                ),
+                StrictMode::Warn => locale.pick(
+                    "Enabled soft strict mode. Guesses contradicting what you already know will be counted as wasted.",
+                    "Sanfter strikter Modus aktiviert. Versuche, die deinem Wissen widersprechen, zählen als verschwendet.",
+                ),
+                StrictMode::NytHard => locale.pick(
+                    "Enabled NYT hard mode. Correct letters must stay in place and present letters must be reused.",
+                    "NYT-Schwermodus aktiviert. Richtige Buchstaben müssen an ihrer Stelle bleiben und vorhandene Buchstaben wiederverwendet werden.",
+                ),
+                StrictMode::Enabled => locale.pick(
+                    "Enabled strict mode.",
+                    "Strikter Modus aktiviert.",
+                ),
+            }),
+            BotMessage::ModeButtonLabel(mode) => String::from(match mode {
+                StrictMode::Enabled => {
+                    locale.pick("Enable strict mode", "Strikten Modus aktivieren")
+                }
+                StrictMode::Warn => locale.pick(
+                    "Enable soft strict mode",
+                    "Sanften strikten Modus aktivieren",
+                ),
+                StrictMode::NytHard => {
+                    locale.pick("Enable NYT hard mode", "NYT-Schwermodus aktivieren")
+                }
+                StrictMode::Disabled => {
+                    locale.pick("Disable strict mode", "Strikten Modus deaktivieren")
+                }
+            }),
+            BotMessage::ShowKeyboardLabel => {
+                String::from(locale.pick("show keyboard", "Tastatur anzeigen"))
+            }
+            BotMessage::ShowKnowledgeLabel => {
+                String::from(locale.pick("show knowledge", "Wissen anzeigen"))
+            }
+            BotMessage::CopyResultLabel(format) => String::from(match format {
+                ShareFormat::Emoji => locale.pick("Copy Result", "Ergebnis kopieren"),
+                ShareFormat::Spoiler => locale.pick(
+                    "Copy Result with spoilers",
+                    "Ergebnis mit Spoilern kopieren",
+                ),
+                ShareFormat::CodeBlock => {
+                    locale.pick("Copy Result as text", "Ergebnis als Text kopieren")
+                }
+            }),
+            BotMessage::CurrentLocale(current) => {
+                let available = Locale::ALL
+                    .iter()
+                    .map(|locale| format!("`{}`", locale.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                locale.pick(
+                    format!(
+                        "Your language is `{}`. Available languages: {available}",
+                        current.name()
+                    ),
+                    format!(
+                        "Deine Sprache ist `{}`. Verfügbare Sprachen: {available}",
+                        current.name()
+                    ),
+                )
+            }
+            BotMessage::LocaleChanged(new) => locale.pick(
+                format!("Your language is now `{}`.", new.name()),
+                format!("Deine Sprache ist jetzt `{}`.", new.name()),
+            ),
+            BotMessage::UnknownLocale(name) => locale.pick(
+                format!("Unknown language `{name}`."),
+                format!("Unbekannte Sprache `{name}`."),
+            ),
+            // Only used for locales other than English; English help is generated by serenity.
+            BotMessage::Help { prefix } => locale.pick(
+                String::new(),
+                format!(
+                    "**Befehle**\n\
+`{prefix}play <code>`: Ein Wort mit dem angegebenen Code erraten.\n\
+`{prefix}random`: Ein zufälliges Wort erraten.\n\
+`{prefix}daily`: Das heutige Wordle erraten.\n\
+`{prefix}encode <wort>`: Ein Wort verschlüsseln, um es mit Freunden zu teilen.\n\
+`{prefix}stats <code>`: Statistiken zu einem Code anzeigen.\n\
+`{prefix}settings`: Einstellungen anzeigen und ändern.\n\
+`{prefix}layout <layout>`: Das Tastaturlayout wählen.\n\
+`{prefix}theme <thema>`: Die Emojis für Spielfeld, Tastatur und Ergebnisse wählen.\n\
+`{prefix}display <image|text|screenreader>`: Wählen, wie das Spielfeld angezeigt wird.\n\
+`{prefix}strict <off|soft|nyt|strict>`: Den strikten Modus für neue Spiele wählen.\n\
+`{prefix}language <en|de>`: Die Sprache wählen.\n\
+`{prefix}help <befehl>`: Mehr über einen Befehl erfahren (auf Englisch)."
+                ),
+            ),
+            BotMessage::KnowledgePattern(pattern) => locale.pick(
+                format!("Pattern: `{pattern}`"),
+                format!("Muster: `{pattern}`"),
+            ),
+            BotMessage::NotInPosition { position, letters } => locale.pick(
+                format!("Not in position {position}: {letters}"),
+                format!("Nicht an Position {position}: {letters}"),
+            ),
+            BotMessage::RequiredLetters(letters) => locale.pick(
+                format!("Required letters: {letters}"),
+                format!("Enthaltene Buchstaben: {letters}"),
+            ),
+            BotMessage::AbsentLetters(letters) => locale.pick(
+                format!("Absent letters: {letters}"),
+                format!("Fehlende Buchstaben: {letters}"),
+            ),
+            BotMessage::KeyboardLegend {
+                correct,
+                present,
+                absent,
+            } => locale.pick(
+                format!("{correct} correct position known · {present} present · {absent} absent · ¹ exact count · ¹⁺ minimum count"),
+                format!("{correct} richtige Position bekannt · {present} vorhanden · {absent} fehlt · ¹ genaue Anzahl · ¹⁺ Mindestanzahl"),
+            ),
+            BotMessage::KeyboardGroup(state) => String::from(match state {
+                LetterState::Correct => locale.pick("Correct", "Richtig"),
+                LetterState::Present => locale.pick("Present", "Vorhanden"),
+                LetterState::Absent => locale.pick("Absent", "Fehlt"),
+                LetterState::Unknown => locale.pick("Not guessed yet", "Noch nicht geraten"),
+            }),
+            BotMessage::SettingsSummary {
+                preferences,
+                prefix,
+            } => {
+                let layout = preferences.keyboard_layout.name();
+                let theme = preferences.theme.name();
+                let display = preferences.display_mode.name();
+                let board = preferences.board_emoji_mode.name();
+                let result = preferences.result_emoji_mode.name();
+                let strict = preferences.default_strict_mode.name();
+                locale.pick(
+                    format!(
+                        "Your settings: keyboard layout `{layout}`, theme `{theme}`, board display `{display}`, \
+board emojis `{board}`, Copy Result emojis `{result}`, default strict mode `{strict}` (change it with `{prefix}strict`)."
+                    ),
+                    format!(
+                        "Deine Einstellungen: Tastaturlayout `{layout}`, Thema `{theme}`, Spielfeldanzeige `{display}`, \
+Spielfeld-Emojis `{board}`, Emojis für Ergebnis kopieren `{result}`, strikter Standardmodus `{strict}` (mit `{prefix}strict` änderbar)."
+                    ),
+                )
+            }
+            BotMessage::SettingsPlaceholder(menu) => String::from(match menu {
+                SettingsMenu::KeyboardLayout => locale.pick("Keyboard layout", "Tastaturlayout"),
+                SettingsMenu::Theme => locale.pick("Emoji theme", "Emoji-Thema"),
+                SettingsMenu::DisplayMode => locale.pick("Board display", "Spielfeldanzeige"),
+                SettingsMenu::BoardEmojiMode => locale.pick("Board emojis", "Spielfeld-Emojis"),
+                SettingsMenu::ResultEmojiMode => {
+                    locale.pick("Copy Result emojis", "Emojis für Ergebnis kopieren")
+                }
+            }),
+            BotMessage::CurrentLayout { current, available } => locale.pick(
+                format!(
+                    "Your keyboard layout is `{}`. Available layouts: {available}",
+                    current.name()
+                ),
+                format!(
+                    "Dein Tastaturlayout ist `{}`. Verfügbare Layouts: {available}",
+                    current.name()
+                ),
+            ),
+            BotMessage::LayoutChanged(layout) => locale.pick(
+                format!("Your keyboard layout is now `{}`.", layout.name()),
+                format!("Dein Tastaturlayout ist jetzt `{}`.", layout.name()),
+            ),
+            BotMessage::UnknownLayout { name, available } => locale.pick(
+                format!("Unknown keyboard layout `{name}`. Available layouts: {available}"),
+                format!("Unbekanntes Tastaturlayout `{name}`. Verfügbare Layouts: {available}"),
+            ),
+            BotMessage::CurrentTheme { current, available } => locale.pick(
+                format!("Your theme is `{current}`. Available themes: {available}"),
+                format!("Dein Thema ist `{current}`. Verfügbare Themen: {available}"),
+            ),
+            BotMessage::ThemeChanged(theme) => locale.pick(
+                format!("Your theme is now `{theme}`."),
+                format!("Dein Thema ist jetzt `{theme}`."),
+            ),
+            BotMessage::UnknownTheme { name, available } => locale.pick(
+                format!("Unknown theme `{name}`. Available themes: {available}"),
+                format!("Unbekanntes Thema `{name}`. Verfügbare Themen: {available}"),
+            ),
+            BotMessage::NotSingleEmoji(value) => locale.pick(
+                format!("{value} is not a single emoji. Please use Unicode emojis or custom emojis like `<:name:id>`."),
+                format!("{value} ist kein einzelnes Emoji. Bitte verwende Unicode-Emojis oder eigene Emojis wie `<:name:id>`."),
+            ),
+            BotMessage::CustomThemeNeedsThreeEmojis => String::from(locale.pick(
+                "Please provide exactly three emojis for absent, present and correct letters.",
+                "Bitte gib genau drei Emojis für fehlende, vorhandene und richtige Buchstaben an.",
+            )),
+            BotMessage::CurrentDisplayMode { current, available } => locale.pick(
+                format!(
+                    "Your board is shown as `{}`. Available modes: {available}",
+                    current.name()
+                ),
+                format!(
+                    "Dein Spielfeld wird als `{}` angezeigt. Verfügbare Modi: {available}",
+                    current.name()
+                ),
+            ),
+            BotMessage::DisplayModeChanged(mode) => locale.pick(
+                format!("Your board is now shown as `{}`.", mode.name()),
+                format!("Dein Spielfeld wird jetzt als `{}` angezeigt.", mode.name()),
+            ),
+            BotMessage::UnknownDisplayMode { name, available } => locale.pick(
+                format!("Unknown display mode `{name}`. Available modes: {available}"),
+                format!("Unbekannter Anzeigemodus `{name}`. Verfügbare Modi: {available}"),
+            ),
+            BotMessage::CurrentStrictMode { current, available } => locale.pick(
+                format!(
+                    "New games start in strict mode `{}`. Available modes: {available}",
+                    current.name()
+                ),
+                format!(
+                    "Neue Spiele beginnen im strikten Modus `{}`. Verfügbare Modi: {available}",
+                    current.name()
+                ),
+            ),
+            BotMessage::StrictModeChanged(mode) => locale.pick(
+                format!("New games now start in strict mode `{}`.", mode.name()),
+                format!("Neue Spiele beginnen jetzt im strikten Modus `{}`.", mode.name()),
+            ),
+            BotMessage::UnknownStrictMode { name, available } => locale.pick(
+                format!("Unknown strict mode `{name}`. Available modes: {available}"),
+                format!("Unbekannter strikter Modus `{name}`. Verfügbare Modi: {available}"),
+            ),
+            BotMessage::UnknownRandomOption(option) => locale.pick(
+                format!("Unknown option: `{option}`"),
+                format!("Unbekannte Option: `{option}`"),
+            ),
+            BotMessage::NoMatchingWord => String::from(locale.pick(
+                "No word in the word list matches these options.",
+                "Kein Wort in der Wortliste passt zu diesen Optionen.",
+            )),
+            BotMessage::ShareRandomWord => String::from(locale.pick(
+                "Want to share this word with friends after you're done? They can use the following command.",
+                "Möchtest du dieses Wort danach mit Freunden teilen? Sie können den folgenden Befehl verwenden.",
+            )),
+            BotMessage::MissingCode => String::from(locale.pick(
+                "Please provide a valid code.",
+                "Bitte gib einen gültigen Code an.",
+            )),
+            BotMessage::InvalidCode(code) => locale.pick(
+                format!("Invalid code: {code}"),
+                format!("Ungültiger Code: {code}"),
+            ),
+            BotMessage::CodeDifficulty { code, difficulty } => {
+                format!("Friendle `{code}`: {}", difficulty.localize(locale))
+            }
+            BotMessage::NobodyPlayedYet => String::from(locale.pick(
+                "Nobody has played this code yet.",
+                "Diesen Code hat noch niemand gespielt.",
+            )),
+            BotMessage::CodeStats { started, won, lost } => locale.pick(
+                format!("Played: {started}, won: {won}, lost: {lost}"),
+                format!("Gespielt: {started}, gewonnen: {won}, verloren: {lost}"),
+            ),
+            BotMessage::MissingWord => {
+                String::from(locale.pick("Please provide a word.", "Bitte gib ein Wort an."))
+            }
+            BotMessage::InvalidWordFormat(word) => locale.pick(
+                format!("Invalid word format: {word}. Only five-letter words with letters a-z are supported."),
+                format!("Ungültiges Wortformat: {word}. Nur Wörter aus fünf Buchstaben von a-z werden unterstützt."),
+            ),
+            BotMessage::ShareEncodedWord => String::from(locale.pick(
+                "To play a game of Wordle with your secret word, use the following command.",
+                "Um eine Runde Wordle mit deinem geheimen Wort zu spielen, verwende den folgenden Befehl.",
+            )),
+            BotMessage::WordNotInWordList => String::from(locale.pick(
+                "Note that your word is not in the original Wordle word list.",
+                "Beachte, dass dein Wort nicht in der ursprünglichen Wordle-Wortliste steht.",
+            )),
+            BotMessage::DailyUnavailable(date) => locale.pick(
+                format!("could not acquire Wordle solution for `{date}`"),
+                format!("Die Wordle-Lösung für `{date}` konnte nicht abgerufen werden."),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_german_messages_carry_data() {
+        assert_eq!(
+            BotMessage::Lost { solution: "tales" }.localize(Locale::De),
+            "Leider hast du keine Versuche mehr. Die Lösung war ||`tales`||"
+        );
+        assert_eq!(
+            BotMessage::DidYouMean(&[String::from("tales"), String::from("tiles")])
+                .localize(Locale::En),
+            "Did you mean `tales`, `tiles`?"
+        );
+    }
+
+    #[test]
+    fn test_german_help_lists_all_commands() {
+        let help = BotMessage::Help { prefix: "!" }.localize(Locale::De);
+        for command in ["!layout", "!theme", "!display", "!strict", "!settings"] {
+            assert!(help.contains(&format!("`{command}")), "{command}");
+        }
+        assert!(!help.contains("`."));
+    }
+}
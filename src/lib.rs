@@ -0,0 +1,23 @@
+//! The Discord frontend of Friendle, split from the binary so fuzz targets can use it.
+
+pub mod api;
+pub mod board_image;
+pub mod buttons;
+pub mod commands;
+pub mod config;
+pub mod event_handler;
+pub mod game_loop_hook;
+pub mod game_store;
+pub mod i18n;
+pub mod logging;
+pub mod metrics;
+pub mod preferences;
+pub mod render;
+pub mod shutdown;
+pub mod stats;
+pub mod storage;
+pub mod transport;
+pub mod util;
+pub mod wordlist;
+
+use friendle_core::{constants, model};
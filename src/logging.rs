@@ -0,0 +1,88 @@
+use serenity::async_trait;
+use serenity::client::Context;
+use serenity::framework::standard::macros::hook;
+use serenity::framework::standard::CommandResult;
+use serenity::framework::Framework;
+use serenity::model::channel::Message;
+use tracing::{field, info_span, warn, Instrument, Span};
+use tracing_subscriber::EnvFilter;
+
+pub const LOG_LEVEL_ENV_VAR: &str = "LOG_LEVEL";
+pub const LOG_FORMAT_ENV_VAR: &str = "LOG_FORMAT";
+
+/// Output format of log lines.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LogFormat {
+    /// Human-readable, for terminals.
+    Pretty,
+    /// One JSON object per line, for journald and fly.io log shipping.
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!(
+                "Unknown log format `{s}`, expected `pretty` or `json`"
+            )),
+        }
+    }
+}
+
+/// Installs the global subscriber. The level accepts `tracing` filter directives,
+/// e.g. `info` or `friendle=debug,serenity=warn`.
+pub fn init(level: &str, format: LogFormat) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_new(level)?;
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Pretty => builder.try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    }
+    .map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Wraps the framework so every message, and the command or guess it triggers,
+/// is handled in a span carrying the author.
+pub struct TracedFramework<F>(pub F);
+
+#[async_trait]
+impl<F: Framework> Framework for TracedFramework<F> {
+    async fn dispatch(&self, ctx: Context, msg: Message) {
+        let span = info_span!(
+            "message",
+            user_id = msg.author.id.0,
+            dm = msg.guild_id.is_none(),
+            command = field::Empty,
+        );
+        self.0.dispatch(ctx, msg).instrument(span).await;
+    }
+}
+
+#[hook]
+pub async fn before_command(_: &Context, _: &Message, command_name: &str) -> bool {
+    Span::current().record("command", command_name);
+    true
+}
+
+#[hook]
+pub async fn after_command(_: &Context, _: &Message, command_name: &str, result: CommandResult) {
+    if let Err(err) = result {
+        warn!(command = command_name, "Command failed: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!("json".parse(), Ok(LogFormat::Json));
+        assert_eq!("Pretty".parse(), Ok(LogFormat::Pretty));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
@@ -1,94 +1,173 @@
-mod buttons;
-mod commands;
-mod constants;
-mod event_handler;
-mod game_loop_hook;
-mod model;
-mod player;
-mod util;
-mod wordlist;
-
-use commands::daily::*;
-use commands::encode::*;
-use commands::help::*;
-use commands::play::*;
-
-use event_handler::Handler;
-use game_loop_hook::message_hook;
-use model::validate_word::validate_word_format;
-use player::PlayerState;
-use wordlist::WordList;
+use friendle::commands::daily::*;
+use friendle::commands::display::*;
+use friendle::commands::encode::*;
+use friendle::commands::help::*;
+use friendle::commands::language::*;
+use friendle::commands::layout::*;
+use friendle::commands::play::*;
+use friendle::commands::random::*;
+use friendle::commands::settings::*;
+use friendle::commands::stats::*;
+use friendle::commands::strict::*;
+use friendle::commands::theme::*;
+
+use friendle_core::word_list::parse_word_list;
+
+use anyhow::Context;
+use friendle::api::ApiState;
+use friendle::config::Config;
+use friendle::event_handler::{GatewayEventRecorder, Handler};
+use friendle::game_loop_hook::message_hook;
+use friendle::game_store::GameStore;
+use friendle::logging::{after_command, before_command, TracedFramework};
+use friendle::metrics::{GatewayStatus, Metrics};
+use friendle::preferences::{self, PreferenceStore, UserPreferences};
+use friendle::shutdown::{Shutdown, ShutdownAwareFramework, HANDLER_TIMEOUT};
+use friendle::stats::CodeStatsStore;
+use friendle::wordlist::WordList;
 
 use serenity::client::Client;
 use serenity::framework::standard::{macros::group, StandardFramework};
+use serenity::model::id::UserId;
 
-use std::collections::HashSet;
 use std::env;
-use std::error::Error;
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use tracing::{error, info, warn};
 
 #[group]
-#[commands(encode, play, daily)]
+#[commands(
+    encode, play, daily, random, stats, layout, theme, display, strict, settings, language
+)]
 struct General;
 
-pub const WORD_LIST_PATH_ENV_VAR: &str = "WORD_LIST_PATH";
-pub const DISCORD_TOKEN: &str = "FRIENDLE_DISCORD_TOKEN";
-pub const DISCORD_APPLICATION_ID: &str = "FRIENDLE_APPLICATION_ID";
-
 #[tokio::main]
-pub async fn main() -> Result<(), Box<dyn Error>> {
-    // The Application Id is usually the Bot User Id. It is needed for components
-    let application_id: u64 = env::var(DISCORD_APPLICATION_ID)
-        .unwrap_or_else(|_| panic!("{DISCORD_APPLICATION_ID} not set"))
-        .parse()
-        .expect("application id is not a valid id");
-
-    let word_list_path = std::env::var(WORD_LIST_PATH_ENV_VAR)
-        .unwrap_or_else(|_| String::from("resources/wordlist.txt"));
-    println!("word list path env var: {}", word_list_path);
-
-    let word_list_path = PathBuf::from(word_list_path);
-
-    let words_string = std::fs::read_to_string(&word_list_path).expect("Failed to load word list");
-    let word_lines = words_string.lines();
-    let mut words: HashSet<String> = HashSet::new();
-    for word in word_lines {
-        match validate_word_format(word) {
-            Ok(_) => {
-                words.insert(String::from(word));
-            }
-            Err(err) => {
-                eprintln!("{err}");
-            }
-        }
+pub async fn main() -> anyhow::Result<()> {
+    let args = env::args().collect::<Vec<_>>();
+    let config = Config::load(&args)?;
+    friendle::logging::init(&config.log_level, config.log_format)?;
+
+    info!(path = %config.word_list_path.display(), "Loading word list");
+    let words_string = std::fs::read_to_string(&config.word_list_path).with_context(|| {
+        format!(
+            "Failed to load word list {}",
+            config.word_list_path.display()
+        )
+    })?;
+    let (words, errors) = parse_word_list(&words_string);
+    for err in errors {
+        warn!("Skipping invalid word in word list: {err}");
     }
-    let word_list = WordList { words };
+    let word_list = WordList::new(words);
+
+    let mut preference_store = PreferenceStore::load(config.preferences_path.clone())?;
+    preference_store.set_defaults(UserPreferences {
+        default_strict_mode: config.default_strict_mode,
+        ..UserPreferences::default()
+    });
+
+    let games = GameStore::load(config.games_path.clone())?;
+    let stats = CodeStatsStore::load(config.stats_path.clone())?;
+    info!(
+        games = games.len(),
+        codes = stats.stats_per_code.len(),
+        "Restored saved games and statistics"
+    );
 
+    let owners = config.owner_ids.iter().copied().map(UserId).collect();
     let framework = StandardFramework::new()
-        .configure(|c| c.prefix("."))
+        .configure(|c| c.prefix(&config.prefix).owners(owners))
         .help(&MY_HELP)
+        .before(before_command)
+        .after(after_command)
         .normal_message(message_hook)
         .group(&GENERAL_GROUP);
 
-    // Login with a bot token from the environment
-    let token = env::var(DISCORD_TOKEN).expect("token");
-    let mut client = Client::builder(token)
+    let mut client = Client::builder(&config.discord_token)
         .event_handler(Handler)
-        .framework(framework)
-        .application_id(application_id)
+        .raw_event_handler(GatewayEventRecorder)
+        .framework(TracedFramework(ShutdownAwareFramework(framework)))
+        // The Application Id is usually the Bot User Id. It is needed for components
+        .application_id(config.application_id)
         .await
-        .expect("Error creating client");
+        .context("Error creating client")?;
 
+    // The HTTP API shares its stores with the Discord frontend.
+    let api_state = ApiState {
+        games: Arc::new(games),
+        stats: Arc::new(Mutex::new(stats)),
+        preferences: Arc::new(Mutex::new(preference_store)),
+        word_list: Arc::new(word_list),
+        metrics: Arc::default(),
+        gateway_status: Arc::default(),
+        shutdown: Arc::default(),
+        api_tokens: Arc::new(config.api_tokens.clone()),
+    };
     {
         let mut data = client.data.write().await;
-        data.insert::<WordList>(Arc::new(word_list));
-        data.insert::<PlayerState>(Arc::new(Mutex::new(PlayerState::default())));
+        data.insert::<WordList>(api_state.word_list.clone());
+        data.insert::<GameStore>(api_state.games.clone());
+        data.insert::<CodeStatsStore>(api_state.stats.clone());
+        data.insert::<PreferenceStore>(api_state.preferences.clone());
+        data.insert::<Metrics>(api_state.metrics.clone());
+        data.insert::<GatewayStatus>(api_state.gateway_status.clone());
+        data.insert::<Shutdown>(api_state.shutdown.clone());
     }
 
+    let (http_host, http_port) = (config.http_host, config.http_port);
+    client.data.write().await.insert::<Config>(Arc::new(config));
+    let (games, stats, preferences, shutdown) = (
+        api_state.games.clone(),
+        api_state.stats.clone(),
+        api_state.preferences.clone(),
+        api_state.shutdown.clone(),
+    );
+    tokio::spawn(preferences::flush_periodically(preferences.clone()));
+    tokio::spawn(async move {
+        if let Err(why) = friendle::api::serve(http_host, http_port, api_state).await {
+            error!("HTTP API stopped: {why}");
+        }
+    });
+
+    // On SIGINT or SIGTERM, stop taking new guesses and give running handlers time to finish
+    // before disconnecting, which makes `client.start()` return.
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            if let Err(why) = friendle::shutdown::signal().await {
+                error!("Failed to listen for shutdown signals: {why}");
+                return;
+            }
+            info!("Shutting down");
+            shutdown.request();
+            shutdown.wait_for_handlers(HANDLER_TIMEOUT).await;
+            shard_manager.lock().await.shutdown_all().await;
+        }
+    });
+
     if let Err(why) = client.start().await {
-        println!("An error occurred while running the client: {:?}", why);
+        error!("An error occurred while running the client: {:?}", why);
+    }
+
+    if let Err(why) = preferences::flush(&preferences).await {
+        error!("Failed to save preferences: {why:#}");
+    }
+    if let Err(why) = games.save().await {
+        error!("Failed to save games: {why:#}");
+    }
+    let stats = stats.lock().unwrap();
+    if let Err(why) = stats.save() {
+        error!("Failed to save statistics: {why:#}");
     }
+    let summary = shutdown.summary();
+    info!(
+        handled = summary.handled,
+        rejected = summary.rejected,
+        unfinished = summary.unfinished,
+        games = games.len(),
+        codes = stats.stats_per_code.len(),
+        "Shutdown complete"
+    );
     Ok(())
 }
@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use serenity::prelude::TypeMapKey;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::model::game::{GameState, StrictMode};
+use crate::model::guess_error::{GuessError, InvalidWordError};
+use crate::model::knowledge::KnowledgeValidationError;
+
+/// A Prometheus counter with one value per combination of label values.
+#[derive(Debug)]
+struct CounterFamily {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Mutex<BTreeMap<Vec<&'static str>, u64>>,
+}
+
+impl CounterFamily {
+    const fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn inc(&self, label_values: &[&'static str]) {
+        debug_assert_eq!(label_values.len(), self.labels.len());
+        *self
+            .values
+            .lock()
+            .unwrap()
+            .entry(label_values.to_vec())
+            .or_default() += 1;
+    }
+
+    fn render(&self, output: &mut String) {
+        writeln!(output, "# HELP {} {}", self.name, self.help).unwrap();
+        writeln!(output, "# TYPE {} counter", self.name).unwrap();
+        let values = self.values.lock().unwrap();
+        // Without labels, the single sample is known up front, so report it before the first increment
+        // instead of leaving the series absent.
+        if self.labels.is_empty() && values.is_empty() {
+            writeln!(output, "{} 0", self.name).unwrap();
+        }
+        for (label_values, value) in values.iter() {
+            let labels = self
+                .labels
+                .iter()
+                .zip(label_values)
+                .map(|(label, value)| format!("{label}=\"{value}\""))
+                .collect::<Vec<_>>()
+                .join(",");
+            if labels.is_empty() {
+                writeln!(output, "{} {value}", self.name).unwrap();
+            } else {
+                writeln!(output, "{}{{{labels}}} {value}", self.name).unwrap();
+            }
+        }
+    }
+}
+
+/// Counters exposed on `/metrics` in the Prometheus text format.
+#[derive(Debug)]
+pub struct Metrics {
+    games_started: CounterFamily,
+    games_finished: CounterFamily,
+    guesses_rejected: CounterFamily,
+    daily_fetch_failures: CounterFamily,
+    button_interactions: CounterFamily,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            games_started: CounterFamily::new(
+                "friendle_games_started_total",
+                "Games started, by strict mode.",
+                &["mode"],
+            ),
+            games_finished: CounterFamily::new(
+                "friendle_games_finished_total",
+                "Games finished, by outcome and strict mode.",
+                &["outcome", "mode"],
+            ),
+            guesses_rejected: CounterFamily::new(
+                "friendle_guesses_rejected_total",
+                "Guesses rejected, by error and reason.",
+                &["error", "reason"],
+            ),
+            daily_fetch_failures: CounterFamily::new(
+                "friendle_daily_fetch_failures_total",
+                "Failures fetching the daily Wordle solution.",
+                &[],
+            ),
+            button_interactions: CounterFamily::new(
+                "friendle_button_interactions_total",
+                "Button interactions, by button.",
+                &["button"],
+            ),
+        }
+    }
+}
+
+impl TypeMapKey for Metrics {
+    type Value = Arc<Metrics>;
+}
+
+impl Metrics {
+    pub fn game_started(&self, mode: StrictMode) {
+        self.games_started.inc(&[mode.name()]);
+    }
+
+    pub fn game_finished(&self, state: GameState, mode: StrictMode) {
+        let outcome = match state {
+            GameState::Won => "won",
+            GameState::Lost => "lost",
+            GameState::InProgress => return,
+        };
+        self.games_finished.inc(&[outcome, mode.name()]);
+    }
+
+    pub fn guess_rejected(&self, err: &GuessError) {
+        let labels = match err {
+            GuessError::GameNotInProgress => ["GameNotInProgress", ""],
+            GuessError::InvalidWord(err) => [
+                "InvalidWord",
+                match err {
+                    InvalidWordError::WrongLength { .. } => "WrongLength",
+                    InvalidWordError::NonLatinAlpha => "NonLatinAlpha",
+                    InvalidWordError::NotInWordList { .. } => "NotInWordList",
+                },
+            ],
+            GuessError::RejectedByStrictMode(err) => [
+                "RejectedByStrictMode",
+                match err {
+                    KnowledgeValidationError::UnusedFixedCharacter { .. } => "UnusedFixedCharacter",
+                    KnowledgeValidationError::IncorrectPlacement { .. } => "IncorrectPlacement",
+                    KnowledgeValidationError::WrongCount { .. } => "WrongCount",
+                    KnowledgeValidationError::AbsentLetter { .. } => "AbsentLetter",
+                },
+            ],
+        };
+        self.guesses_rejected.inc(&labels);
+    }
+
+    pub fn daily_fetch_failed(&self) {
+        self.daily_fetch_failures.inc(&[]);
+    }
+
+    pub fn button_interaction(&self, button: &'static str) {
+        self.button_interactions.inc(&[button]);
+    }
+
+    /// All metrics in the Prometheus text format; `active_games` is measured when scraped.
+    pub fn render(&self, active_games: usize) -> String {
+        let mut output = String::new();
+        for family in [
+            &self.games_started,
+            &self.games_finished,
+            &self.guesses_rejected,
+            &self.daily_fetch_failures,
+            &self.button_interactions,
+        ] {
+            family.render(&mut output);
+        }
+        output.push_str("# HELP friendle_active_games Games currently in progress.\n");
+        output.push_str("# TYPE friendle_active_games gauge\n");
+        writeln!(output, "friendle_active_games {active_games}").unwrap();
+        output
+    }
+}
+
+/// Connection state of the Discord gateway, reported on `/healthz`.
+#[derive(Debug, Default)]
+pub struct GatewayStatus {
+    connected: AtomicBool,
+    last_event: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl TypeMapKey for GatewayStatus {
+    type Value = Arc<GatewayStatus>;
+}
+
+impl GatewayStatus {
+    pub fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::Relaxed);
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    pub fn record_event(&self) {
+        *self.last_event.lock().unwrap() = Some(Utc::now());
+    }
+
+    pub fn last_event(&self) -> Option<DateTime<Utc>> {
+        *self.last_event.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = Metrics::default();
+        metrics.game_started(StrictMode::Enabled);
+        metrics.game_started(StrictMode::Enabled);
+        metrics.game_finished(GameState::Won, StrictMode::Disabled);
+        metrics.guess_rejected(&GuessError::InvalidWord(InvalidWordError::NonLatinAlpha));
+        metrics.daily_fetch_failed();
+        metrics.button_interaction("ShowKeyboard");
+
+        let output = metrics.render(3);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert!(lines.contains(&"# TYPE friendle_games_started_total counter"));
+        assert!(lines.contains(&"friendle_games_started_total{mode=\"strict\"} 2"));
+        assert!(lines.contains(&"friendle_games_finished_total{outcome=\"won\",mode=\"off\"} 1"));
+        assert!(lines.contains(
+            &"friendle_guesses_rejected_total{error=\"InvalidWord\",reason=\"NonLatinAlpha\"} 1"
+        ));
+        assert!(lines.contains(&"friendle_daily_fetch_failures_total 1"));
+        assert!(lines.contains(&"friendle_button_interactions_total{button=\"ShowKeyboard\"} 1"));
+        assert!(lines.contains(&"friendle_active_games 3"));
+    }
+
+    #[test]
+    fn test_unlabeled_counters_start_at_zero() {
+        let output = Metrics::default().render(0);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert!(lines.contains(&"friendle_daily_fetch_failures_total 0"));
+        // labeled families have no samples until their label values are known
+        assert!(!output.contains("friendle_games_started_total{"));
+    }
+}
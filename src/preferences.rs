@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use serenity::prelude::TypeMapKey;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Mutex as AsyncMutex;
+use tracing::error;
+
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use crate::i18n::Locale;
+use crate::model::evaluation::{EmojiMode, EmojiTheme};
+use crate::model::game::StrictMode;
+use crate::storage::{load_json, write_atomically};
+use friendle_core::keyboard::KeyboardLayout;
+
+/// How the board is shown after each guess.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    /// A rendered PNG image of the board.
+    #[default]
+    Image,
+    /// Emoji rows, like the shared result.
+    Text,
+    /// Plain sentences instead of emojis, for screen readers.
+    /// Also used for the keyboard and shared results.
+    ScreenReader,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Image,
+        DisplayMode::Text,
+        DisplayMode::ScreenReader,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DisplayMode::Image => "image",
+            DisplayMode::Text => "text",
+            DisplayMode::ScreenReader => "screenreader",
+        }
+    }
+}
+
+impl FromStr for DisplayMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DisplayMode::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPreferences {
+    pub keyboard_layout: KeyboardLayout,
+    pub theme: EmojiTheme,
+    pub display_mode: DisplayMode,
+    /// Emojis used for the board in text display mode.
+    pub board_emoji_mode: EmojiMode,
+    /// Emojis used by the Copy Result button.
+    pub result_emoji_mode: EmojiMode,
+    /// Strict mode new games start in.
+    pub default_strict_mode: StrictMode,
+    pub locale: Locale,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            keyboard_layout: KeyboardLayout::default(),
+            theme: EmojiTheme::default(),
+            display_mode: DisplayMode::default(),
+            board_emoji_mode: EmojiMode::Unicode,
+            // Discord names survive copy-pasting on all platforms.
+            result_emoji_mode: EmojiMode::DiscordName,
+            default_strict_mode: StrictMode::default(),
+            locale: Locale::default(),
+        }
+    }
+}
+
+/// How often changed preferences are written to disk.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+pub struct PreferenceStore {
+    pub preferences_per_user: HashMap<u64, UserPreferences>,
+    /// File the preferences are flushed to; not persisted if unset.
+    path: Option<PathBuf>,
+    /// Preferences of users that never changed anything.
+    defaults: UserPreferences,
+    /// Whether there are changes that haven't been flushed yet.
+    changed: bool,
+    /// Held while flushing, so an older snapshot can't overwrite a newer one.
+    flushing: Arc<AsyncMutex<()>>,
+}
+
+impl TypeMapKey for PreferenceStore {
+    type Value = Arc<Mutex<PreferenceStore>>;
+}
+
+impl PreferenceStore {
+    /// Preferences for the given user; users that never changed anything get the defaults.
+    pub fn get(&self, user_id: u64) -> UserPreferences {
+        self.preferences_per_user
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_else(|| self.defaults.clone())
+    }
+
+    /// Sets the preferences used for users that never changed anything.
+    pub fn set_defaults(&mut self, defaults: UserPreferences) {
+        self.defaults = defaults;
+    }
+
+    /// Loads the preferences saved at the given path, starting out empty if the file doesn't exist yet.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        Ok(Self {
+            preferences_per_user: load_json(&path)?,
+            path: Some(path),
+            ..Self::default()
+        })
+    }
+
+    /// Changes the preferences of the given user. The change is written to disk by the next `flush`.
+    pub fn update(&mut self, user_id: u64, update: impl FnOnce(&mut UserPreferences)) {
+        let defaults = &self.defaults;
+        update(
+            self.preferences_per_user
+                .entry(user_id)
+                .or_insert_with(|| defaults.clone()),
+        );
+        self.changed = true;
+    }
+
+    /// Serializes the preferences if they changed since the last call.
+    fn take_changes(&mut self) -> anyhow::Result<Option<(PathBuf, String)>> {
+        let path = match &self.path {
+            Some(path) if self.changed => path.clone(),
+            _ => return Ok(None),
+        };
+        let json = serde_json::to_string_pretty(&self.preferences_per_user)?;
+        self.changed = false;
+        Ok(Some((path, json)))
+    }
+}
+
+/// Writes changed preferences to disk. Only serializing happens under the store's lock;
+/// the file is written on a blocking thread, so handlers changing preferences never wait for the disk.
+pub async fn flush(store: &Mutex<PreferenceStore>) -> anyhow::Result<()> {
+    let flushing = store.lock().unwrap().flushing.clone();
+    let _flushing = flushing.lock().await;
+    let (path, json) = match store.lock().unwrap().take_changes()? {
+        Some(changes) => changes,
+        None => return Ok(()),
+    };
+    let result = tokio::task::spawn_blocking(move || write_atomically(&path, &json)).await?;
+    if result.is_err() {
+        // try again with the next flush
+        store.lock().unwrap().changed = true;
+    }
+    result
+}
+
+/// Flushes changed preferences every `FLUSH_INTERVAL`, until the task is dropped.
+pub async fn flush_periodically(store: Arc<Mutex<PreferenceStore>>) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(why) = flush(&store).await {
+            error!("Failed to save preferences: {why:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_preferences() {
+        let mut store = PreferenceStore::default();
+        assert_eq!(store.get(1), UserPreferences::default());
+
+        store.update(1, |preferences| {
+            preferences.keyboard_layout = KeyboardLayout::Azerty
+        });
+        assert_eq!(store.get(1).keyboard_layout, KeyboardLayout::Azerty);
+        assert_eq!(store.get(2).keyboard_layout, KeyboardLayout::Qwerty);
+
+        store.update(1, |preferences| preferences.theme = EmojiTheme::Shapes);
+        assert_eq!(store.get(1).keyboard_layout, KeyboardLayout::Azerty);
+        assert_eq!(store.get(1).theme, EmojiTheme::Shapes);
+
+        store.set_defaults(UserPreferences {
+            default_strict_mode: StrictMode::NytHard,
+            ..UserPreferences::default()
+        });
+        assert_eq!(store.get(2).default_strict_mode, StrictMode::NytHard);
+        store.update(2, |preferences| preferences.theme = EmojiTheme::Shapes);
+        assert_eq!(store.get(2).default_strict_mode, StrictMode::NytHard);
+    }
+
+    #[tokio::test]
+    async fn test_preferences_are_persisted() {
+        let path =
+            std::env::temp_dir().join(format!("friendle-preferences-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = Mutex::new(PreferenceStore::load(path.clone()).unwrap());
+        assert!(store.lock().unwrap().preferences_per_user.is_empty());
+        store.lock().unwrap().update(1, |preferences| {
+            preferences.theme = EmojiTheme::Custom(["❌", "⭕", "✅"].map(String::from));
+            preferences.result_emoji_mode = EmojiMode::Unicode;
+        });
+        // changes are only written by a flush
+        assert!(!path.exists());
+        flush(&store).await.unwrap();
+
+        let loaded = PreferenceStore::load(path.clone()).unwrap();
+        assert_eq!(loaded.get(1), store.lock().unwrap().get(1));
+        assert_eq!(loaded.get(2), UserPreferences::default());
+
+        // settings added later fall back to their defaults
+        std::fs::write(&path, r#"{"3": {"display_mode": "Text"}}"#).unwrap();
+        let loaded = PreferenceStore::load(path.clone()).unwrap();
+        assert_eq!(loaded.get(3).display_mode, DisplayMode::Text);
+        assert_eq!(loaded.get(3).result_emoji_mode, EmojiMode::DiscordName);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_display_mode_from_str() {
+        for mode in DisplayMode::ALL {
+            assert_eq!(mode.name().parse(), Ok(mode));
+        }
+        assert_eq!("TEXT".parse(), Ok(DisplayMode::Text));
+        assert_eq!("gif".parse::<DisplayMode>(), Err(()));
+    }
+}
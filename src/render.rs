@@ -0,0 +1,11 @@
+use friendle_core::render::Renderer;
+use serenity::utils::MessageBuilder;
+
+/// Renders game state into a Discord message.
+pub struct DiscordRenderer<'a>(pub &'a mut MessageBuilder);
+
+impl Renderer for DiscordRenderer<'_> {
+    fn push(&mut self, text: &str) {
+        self.0.push(text);
+    }
+}
@@ -0,0 +1,190 @@
+use serenity::async_trait;
+use serenity::client::Context;
+use serenity::framework::Framework;
+use serenity::model::channel::Message;
+use serenity::prelude::TypeMapKey;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+use tracing::debug;
+
+/// How long in-flight handlers get to finish once shutdown begins.
+/// Fly.io kills the bot 5 seconds after sending the signal, systemd after 10.
+pub const HANDLER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Tracks the handlers that are running so shutdown can wait for them,
+/// and turns away new work once shutdown has begun.
+#[derive(Debug)]
+pub struct Shutdown {
+    requested: watch::Sender<bool>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    handled: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            requested: watch::Sender::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            handled: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+}
+
+impl TypeMapKey for Shutdown {
+    type Value = Arc<Shutdown>;
+}
+
+/// Marks a handler as in flight until dropped.
+#[must_use]
+pub struct HandlerGuard(Arc<Shutdown>);
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        self.0.handled.fetch_add(1, Ordering::SeqCst);
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// What happened while the bot was running, logged on shutdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownSummary {
+    pub handled: u64,
+    /// Messages and interactions that arrived after shutdown began.
+    pub rejected: u64,
+    /// Handlers that were still running when the bot stopped waiting for them.
+    pub unfinished: usize,
+}
+
+impl Shutdown {
+    /// Registers a new handler, or returns `None` if shutdown has begun.
+    pub fn start_handler(self: &Arc<Self>) -> Option<HandlerGuard> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        // checked after registering, so `wait_for_handlers` can't miss a handler that got through
+        if self.is_requested() {
+            if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+                self.idle.notify_waiters();
+            }
+            self.rejected.fetch_add(1, Ordering::SeqCst);
+            return None;
+        }
+        Some(HandlerGuard(self.clone()))
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.requested.borrow()
+    }
+
+    /// Begins shutdown; new handlers are turned away from now on.
+    pub fn request(&self) {
+        self.requested.send_replace(true);
+    }
+
+    /// Resolves once shutdown has begun.
+    pub async fn requested(&self) {
+        let mut receiver = self.requested.subscribe();
+        // the sender lives as long as `self`, so this can't fail
+        let _ = receiver.wait_for(|requested| *requested).await;
+    }
+
+    /// Waits until no handlers are running anymore, or the timeout expires.
+    /// Returns the number of handlers that are still running.
+    pub async fn wait_for_handlers(&self, timeout: Duration) -> usize {
+        let idle = async {
+            loop {
+                let notified = self.idle.notified();
+                if self.in_flight.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        let _ = tokio::time::timeout(timeout, idle).await;
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn summary(&self) -> ShutdownSummary {
+        ShutdownSummary {
+            handled: self.handled.load(Ordering::SeqCst),
+            rejected: self.rejected.load(Ordering::SeqCst),
+            unfinished: self.in_flight.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// Resolves on SIGINT (sent by fly.io and Ctrl+C) or SIGTERM (sent by systemd).
+pub async fn signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
+}
+
+/// Wraps the framework so messages, and with them guesses and commands,
+/// are only handled while the bot isn't shutting down.
+pub struct ShutdownAwareFramework<F>(pub F);
+
+#[async_trait]
+impl<F: Framework> Framework for ShutdownAwareFramework<F> {
+    async fn dispatch(&self, ctx: Context, msg: Message) {
+        let shutdown = ctx.data.read().await.get::<Shutdown>().unwrap().clone();
+        match shutdown.start_handler() {
+            Some(_guard) => self.0.dispatch(ctx, msg).await,
+            None => debug!(
+                user_id = msg.author.id.0,
+                "Ignoring message during shutdown"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_handlers() {
+        let shutdown = Arc::new(Shutdown::default());
+        let first = shutdown.start_handler().unwrap();
+        let second = shutdown.start_handler().unwrap();
+        drop(first);
+
+        shutdown.request();
+        shutdown.requested().await;
+        assert!(shutdown.start_handler().is_none());
+        assert_eq!(
+            shutdown.wait_for_handlers(Duration::from_millis(10)).await,
+            1
+        );
+
+        let waiting = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait_for_handlers(Duration::from_secs(10)).await }
+        });
+        tokio::task::yield_now().await;
+        drop(second);
+        assert_eq!(waiting.await.unwrap(), 0);
+        assert_eq!(
+            shutdown.summary(),
+            ShutdownSummary {
+                handled: 2,
+                rejected: 1,
+                unfinished: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use serenity::prelude::TypeMapKey;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::constants::MAX_GUESSES;
+use crate::model::difficulty::Difficulty;
+use crate::model::game::GameState;
+use crate::storage::{load_json, save_json};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeStats {
+    pub difficulty: Difficulty,
+    pub games_started: u32,
+    pub games_won: u32,
+    pub games_lost: u32,
+    /// Number of games won with `i + 1` guesses.
+    pub guess_distribution: [u32; MAX_GUESSES],
+}
+
+impl CodeStats {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            games_started: 0,
+            games_won: 0,
+            games_lost: 0,
+            guess_distribution: [0; MAX_GUESSES],
+        }
+    }
+}
+
+/// Statistics per code, including the estimated difficulty of the encoded word.
+#[derive(Debug, Default)]
+pub struct CodeStatsStore {
+    pub stats_per_code: HashMap<u32, CodeStats>,
+    /// File the statistics are saved to on shutdown; not persisted if unset.
+    path: Option<PathBuf>,
+}
+
+impl TypeMapKey for CodeStatsStore {
+    type Value = Arc<Mutex<CodeStatsStore>>;
+}
+
+impl CodeStatsStore {
+    /// Loads the statistics saved at the given path, starting out empty if the file doesn't exist yet.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        Ok(Self {
+            stats_per_code: load_json(&path)?,
+            path: Some(path),
+        })
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        match &self.path {
+            Some(path) => save_json(path, &self.stats_per_code),
+            None => Ok(()),
+        }
+    }
+
+    /// The statistics for a code, or empty ones for codes nobody has played yet.
+    /// Unlike `get_or_insert`, this doesn't add the code to the store.
+    pub fn get_or_default(&self, code: u32, difficulty: impl FnOnce() -> Difficulty) -> CodeStats {
+        self.stats_per_code
+            .get(&code)
+            .cloned()
+            .unwrap_or_else(|| CodeStats::new(difficulty()))
+    }
+
+    pub fn get_or_insert(
+        &mut self,
+        code: u32,
+        difficulty: impl FnOnce() -> Difficulty,
+    ) -> &mut CodeStats {
+        self.stats_per_code
+            .entry(code)
+            .or_insert_with(|| CodeStats::new(difficulty()))
+    }
+
+    pub fn record_result(&mut self, code: u32, state: GameState, num_guesses: usize) {
+        if let Some(stats) = self.stats_per_code.get_mut(&code) {
+            match state {
+                GameState::Won => {
+                    stats.games_won += 1;
+                    stats.guess_distribution[num_guesses - 1] += 1;
+                }
+                GameState::Lost => stats.games_lost += 1,
+                GameState::InProgress => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_result() {
+        let mut store = CodeStatsStore::default();
+        store.get_or_insert(1234, || Difficulty::Hard).games_started += 2;
+        store.record_result(1234, GameState::Won, 3);
+        store.record_result(1234, GameState::Lost, MAX_GUESSES);
+        // results for unknown codes are ignored
+        store.record_result(42, GameState::Won, 1);
+
+        let stats = store.get_or_insert(1234, || Difficulty::Easy);
+        assert_eq!(stats.difficulty, Difficulty::Hard);
+        assert_eq!(stats.games_started, 2);
+        assert_eq!(stats.games_won, 1);
+        assert_eq!(stats.games_lost, 1);
+        assert_eq!(stats.guess_distribution, [0, 0, 1, 0, 0, 0]);
+        assert!(!store.stats_per_code.contains_key(&42));
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// Reads the JSON saved at the given path, falling back to the default if the file doesn't exist yet.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> anyhow::Result<T> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    write_atomically(path, &serde_json::to_string_pretty(value)?)
+}
+
+pub fn write_atomically(path: &Path, contents: &str) -> anyhow::Result<()> {
+    // write to a temporary file first so a crash can't leave behind a truncated file
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,370 @@
+use serenity::async_trait;
+use serenity::prelude::TypeMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::{ButtonClick, IncomingMessage, OutgoingMessage, Transport};
+use crate::api::{DEFAULT_HTTP_HOST, DEFAULT_HTTP_PORT};
+use crate::buttons::FriendleButton;
+use crate::config::Config;
+use crate::game_store::GameStore;
+use crate::i18n::Locale;
+use crate::logging::LogFormat;
+use crate::metrics::Metrics;
+use crate::model::game::StrictMode;
+use crate::preferences::PreferenceStore;
+use crate::stats::CodeStatsStore;
+use crate::wordlist::WordList;
+
+/// Something the bot sent through the fake transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sent {
+    Reply {
+        to: u64,
+        content: String,
+    },
+    Message {
+        id: u64,
+        channel_id: u64,
+        message: OutgoingMessage,
+    },
+    Response {
+        interaction_id: u64,
+        content: String,
+    },
+    EditedButtons {
+        message_id: u64,
+        buttons: Vec<Vec<FriendleButton>>,
+    },
+}
+
+/// Records everything the bot sends instead of talking to Discord,
+/// and keeps track of the buttons on the sent messages so tests can click them.
+#[derive(Debug, Default)]
+pub struct FakeTransport {
+    sent: Mutex<Vec<Sent>>,
+    buttons_per_message: Mutex<HashMap<u64, Vec<Vec<FriendleButton>>>>,
+    next_id: Mutex<u64>,
+}
+
+impl FakeTransport {
+    fn next_id(&self) -> u64 {
+        let mut next_id = self.next_id.lock().unwrap();
+        *next_id += 1;
+        *next_id
+    }
+
+    /// Everything sent since the last call.
+    pub fn take_sent(&self) -> Vec<Sent> {
+        std::mem::take(&mut self.sent.lock().unwrap())
+    }
+
+    /// A message sent by the given user in their DMs with the bot.
+    pub fn message(&self, user_id: u64, content: &str) -> IncomingMessage {
+        IncomingMessage {
+            id: self.next_id(),
+            channel_id: user_id,
+            author_id: user_id,
+            in_guild: false,
+            content: String::from(content),
+        }
+    }
+
+    /// A click on a button of a message the bot sent earlier.
+    pub fn click(&self, user_id: u64, message_id: u64, button: FriendleButton) -> ButtonClick {
+        let buttons = self.buttons_per_message.lock().unwrap()[&message_id]
+            .iter()
+            .map(|row| row.iter().map(|button| String::from(button.id())).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert!(
+            buttons.iter().flatten().any(|id| id == button.id()),
+            "message {message_id} has no {} button",
+            button.id()
+        );
+        ButtonClick {
+            interaction_id: self.next_id(),
+            token: String::from("token"),
+            user_id,
+            channel_id: user_id,
+            message_id,
+            custom_id: String::from(button.id()),
+            buttons,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn reply(&self, to: &IncomingMessage, content: String) -> anyhow::Result<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push(Sent::Reply { to: to.id, content });
+        Ok(())
+    }
+
+    async fn send_message(&self, channel_id: u64, message: OutgoingMessage) -> anyhow::Result<()> {
+        let id = self.next_id();
+        self.buttons_per_message
+            .lock()
+            .unwrap()
+            .insert(id, message.buttons.clone());
+        self.sent.lock().unwrap().push(Sent::Message {
+            id,
+            channel_id,
+            message,
+        });
+        Ok(())
+    }
+
+    async fn respond(&self, click: &ButtonClick, content: String) -> anyhow::Result<()> {
+        self.sent.lock().unwrap().push(Sent::Response {
+            interaction_id: click.interaction_id,
+            content,
+        });
+        Ok(())
+    }
+
+    async fn edit_buttons(
+        &self,
+        click: &ButtonClick,
+        buttons: Vec<Vec<FriendleButton>>,
+        _: Locale,
+    ) -> anyhow::Result<()> {
+        self.buttons_per_message
+            .lock()
+            .unwrap()
+            .insert(click.message_id, buttons.clone());
+        self.sent.lock().unwrap().push(Sent::EditedButtons {
+            message_id: click.message_id,
+            buttons,
+        });
+        Ok(())
+    }
+}
+
+/// The stores the handlers expect in the context data, with the given word list.
+/// Commands use `!` as their prefix, so replies that mention commands must not hard-code `.`.
+pub fn test_data(words: &[&str]) -> TypeMap {
+    let mut data = TypeMap::new();
+    data.insert::<Config>(Arc::new(Config {
+        discord_token: String::from("token"),
+        application_id: 1,
+        prefix: String::from("!"),
+        word_list_path: PathBuf::new(),
+        preferences_path: PathBuf::new(),
+        games_path: PathBuf::new(),
+        stats_path: PathBuf::new(),
+        daily_url: String::new(),
+        default_strict_mode: StrictMode::default(),
+        http_host: DEFAULT_HTTP_HOST,
+        http_port: DEFAULT_HTTP_PORT,
+        api_tokens: HashMap::new(),
+        owner_ids: HashSet::new(),
+        log_level: String::from("info"),
+        log_format: LogFormat::Pretty,
+    }));
+    data.insert::<WordList>(Arc::new(WordList::new(HashSet::from_iter(
+        words.iter().map(|word| String::from(*word)),
+    ))));
+    data.insert::<GameStore>(Arc::default());
+    data.insert::<CodeStatsStore>(Arc::default());
+    data.insert::<PreferenceStore>(Arc::default());
+    data.insert::<Metrics>(Arc::default());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buttons::copy_result_button::{CopyResultButton, ShareFormat};
+    use crate::buttons::mode_button::ModeButton;
+    use crate::commands::encode::handle_encode;
+    use crate::commands::play::handle_play;
+    use crate::game_loop_hook::handle_message;
+
+    const ALICE: u64 = 1;
+    const BOB: u64 = 2;
+
+    fn words() -> TypeMap {
+        test_data(&["tales", "lanes", "gales", "crane"])
+    }
+
+    /// Id and buttons of the only message in `sent`.
+    fn sent_message(sent: &[Sent]) -> (u64, &OutgoingMessage) {
+        let messages = sent
+            .iter()
+            .filter_map(|sent| match sent {
+                Sent::Message { id, message, .. } => Some((*id, message)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1, "expected one message in {sent:?}");
+        messages[0]
+    }
+
+    fn replies(sent: &[Sent]) -> Vec<&str> {
+        sent.iter()
+            .filter_map(|sent| match sent {
+                Sent::Reply { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn mode_buttons(modes: &[StrictMode]) -> Vec<FriendleButton> {
+        modes
+            .iter()
+            .map(|mode| FriendleButton::ModeChangeButton(ModeButton::new(*mode)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_encode_play_win_and_copy_result() {
+        let data = words();
+        let transport = FakeTransport::default();
+
+        // Alice encodes a word and passes the command on to Bob.
+        let msg = transport.message(ALICE, ".encode tales");
+        handle_encode(&data, &transport, &msg).await.unwrap();
+        let sent = transport.take_sent();
+        let play_command = replies(&sent)[1].to_string();
+        assert!(play_command.starts_with("!play `"));
+
+        let msg = transport.message(BOB, &play_command);
+        handle_play(&data, &transport, &msg).await.unwrap();
+        let sent = transport.take_sent();
+        let (_, intro) = sent_message(&sent);
+        assert!(intro.content.contains("You can now start guessing."));
+        assert_eq!(
+            intro.buttons,
+            vec![mode_buttons(&[
+                StrictMode::Warn,
+                StrictMode::NytHard,
+                StrictMode::Enabled
+            ])]
+        );
+
+        let msg = transport.message(BOB, "lanes");
+        handle_message(&data, &transport, &msg).await.unwrap();
+        let sent = transport.take_sent();
+        let (board_id, board) = sent_message(&sent);
+        assert!(board.content.contains("1/6"));
+        assert!(board.image.is_some());
+        assert_eq!(
+            board.buttons[0],
+            vec![FriendleButton::ShowKeyboard, FriendleButton::ShowKnowledge]
+        );
+
+        // Switching to strict mode replaces the mode buttons with the ones still available.
+        let strict = FriendleButton::ModeChangeButton(ModeButton::new(StrictMode::Enabled));
+        let click = transport.click(BOB, board_id, strict);
+        strict.handle_click(&data, &transport, &click).await;
+        let sent = transport.take_sent();
+        assert_eq!(
+            sent,
+            vec![
+                Sent::Response {
+                    interaction_id: click.interaction_id,
+                    content: String::from("Enabled strict mode.\n"),
+                },
+                Sent::EditedButtons {
+                    message_id: board_id,
+                    buttons: vec![
+                        vec![FriendleButton::ShowKeyboard, FriendleButton::ShowKnowledge],
+                        mode_buttons(&[
+                            StrictMode::Disabled,
+                            StrictMode::Warn,
+                            StrictMode::NytHard
+                        ]),
+                    ],
+                },
+            ]
+        );
+
+        // Clicked buttons are removed.
+        let click = transport.click(BOB, board_id, FriendleButton::ShowKnowledge);
+        FriendleButton::ShowKnowledge
+            .handle_click(&data, &transport, &click)
+            .await;
+        let sent = transport.take_sent();
+        assert!(matches!(&sent[0], Sent::Response { .. }));
+        assert!(matches!(&sent[1], Sent::EditedButtons { buttons, .. }
+            if buttons[0] == vec![FriendleButton::ShowKeyboard]));
+
+        let msg = transport.message(BOB, "tales");
+        handle_message(&data, &transport, &msg).await.unwrap();
+        let sent = transport.take_sent();
+        assert_eq!(replies(&sent), vec!["You won! Good job :)"]);
+        let (result_id, result) = sent_message(&sent);
+        assert_eq!(
+            result.buttons,
+            vec![CopyResultButton::ALL
+                .map(FriendleButton::CopyResultButton)
+                .to_vec()]
+        );
+
+        let copy = FriendleButton::CopyResultButton(CopyResultButton::new(ShareFormat::Emoji));
+        let click = transport.click(BOB, result_id, copy);
+        copy.handle_click(&data, &transport, &click).await;
+        let sent = transport.take_sent();
+        match &sent[0] {
+            Sent::Response { content, .. } => {
+                let code = play_command.trim_start_matches("!play ");
+                assert!(content.starts_with(&format!("Friendle {code}: 2/6**")));
+            }
+            sent => panic!("expected a response, got {sent:?}"),
+        }
+
+        let stats = data.get::<CodeStatsStore>().unwrap().lock().unwrap();
+        let stats = stats.stats_per_code.values().next().unwrap();
+        assert_eq!(stats.games_started, 1);
+        assert_eq!(stats.games_won, 1);
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_not_guesses() {
+        let data = words();
+        let transport = FakeTransport::default();
+
+        // Without a game, a guess would be answered with "No game in progress."
+        let msg = transport.message(ALICE, "!settings");
+        handle_message(&data, &transport, &msg).await.unwrap();
+        assert_eq!(transport.take_sent(), vec![]);
+
+        // Only the configured prefix marks commands.
+        let msg = transport.message(ALICE, ".tales");
+        handle_message(&data, &transport, &msg).await.unwrap();
+        assert_eq!(
+            replies(&transport.take_sent()),
+            vec!["No game in progress."]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_guess_without_game() {
+        let data = words();
+        let transport = FakeTransport::default();
+
+        let msg = transport.message(ALICE, "tales");
+        handle_message(&data, &transport, &msg).await.unwrap();
+        assert_eq!(
+            transport.take_sent(),
+            vec![Sent::Reply {
+                to: msg.id,
+                content: String::from("No game in progress."),
+            }]
+        );
+
+        let msg = IncomingMessage {
+            in_guild: true,
+            ..transport.message(ALICE, "tales")
+        };
+        handle_message(&data, &transport, &msg).await.unwrap();
+        assert_eq!(
+            replies(&transport.take_sent()),
+            vec!["Guesses are only accepted in DMs"]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+pub mod fake;
+
+use serenity::async_trait;
+use serenity::builder::{CreateInteractionResponse, ParseValue};
+use serenity::client::Context;
+use serenity::http::{AttachmentType, Http};
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, MessageId};
+use serenity::model::interactions::message_component::{
+    ActionRowComponent, MessageComponentInteraction,
+};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::buttons::FriendleButton;
+use crate::i18n::Locale;
+
+/// The parts of a received message the handlers look at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    pub id: u64,
+    pub channel_id: u64,
+    pub author_id: u64,
+    /// Whether the message was sent in a server rather than a DM.
+    pub in_guild: bool,
+    pub content: String,
+}
+
+impl From<&Message> for IncomingMessage {
+    fn from(msg: &Message) -> Self {
+        Self {
+            id: msg.id.0,
+            channel_id: msg.channel_id.0,
+            author_id: msg.author.id.0,
+            in_guild: msg.guild_id.is_some(),
+            content: msg.content.clone(),
+        }
+    }
+}
+
+/// A click on a button of one of the bot's messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonClick {
+    pub interaction_id: u64,
+    pub token: String,
+    pub user_id: u64,
+    pub channel_id: u64,
+    pub message_id: u64,
+    /// Id of the clicked button.
+    pub custom_id: String,
+    /// Ids of all buttons on the message, by action row.
+    pub buttons: Vec<Vec<String>>,
+}
+
+impl From<&MessageComponentInteraction> for ButtonClick {
+    fn from(mci: &MessageComponentInteraction) -> Self {
+        Self {
+            interaction_id: mci.id.0,
+            token: mci.token.clone(),
+            user_id: mci.user.id.0,
+            channel_id: mci.channel_id.0,
+            message_id: mci.message.id.0,
+            custom_id: mci.data.custom_id.clone(),
+            buttons: mci
+                .message
+                .components
+                .iter()
+                .map(|row| {
+                    row.components
+                        .iter()
+                        .filter_map(|component| match component {
+                            ActionRowComponent::Button(button) => button.custom_id.clone(),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A message the bot sends, with an optional board image and rows of buttons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingMessage {
+    pub content: String,
+    /// PNG attached as `board.png`.
+    pub image: Option<Vec<u8>>,
+    pub buttons: Vec<Vec<FriendleButton>>,
+    /// Language of the button labels.
+    pub locale: Locale,
+}
+
+/// Everything the handlers send to Discord, so they can be run against a fake in tests.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Replies to the message without pinging its author.
+    async fn reply(&self, to: &IncomingMessage, content: String) -> anyhow::Result<()>;
+
+    async fn send_message(&self, channel_id: u64, message: OutgoingMessage) -> anyhow::Result<()>;
+
+    /// Answers a button click with a new message.
+    async fn respond(&self, click: &ButtonClick, content: String) -> anyhow::Result<()>;
+
+    /// Replaces the buttons of the clicked message, keeping its content.
+    async fn edit_buttons(
+        &self,
+        click: &ButtonClick,
+        buttons: Vec<Vec<FriendleButton>>,
+        locale: Locale,
+    ) -> anyhow::Result<()>;
+}
+
+pub struct DiscordTransport(pub Arc<Http>);
+
+impl From<&Context> for DiscordTransport {
+    fn from(ctx: &Context) -> Self {
+        Self(ctx.http.clone())
+    }
+}
+
+#[async_trait]
+impl Transport for DiscordTransport {
+    async fn reply(&self, to: &IncomingMessage, content: String) -> anyhow::Result<()> {
+        ChannelId(to.channel_id)
+            .send_message(&self.0, |m| {
+                m.reference_message((ChannelId(to.channel_id), MessageId(to.id)))
+                    .allowed_mentions(|mentions| {
+                        // like `Message::reply`: only the replied-to user isn't pinged
+                        mentions
+                            .replied_user(false)
+                            .parse(ParseValue::Everyone)
+                            .parse(ParseValue::Users)
+                            .parse(ParseValue::Roles)
+                    })
+                    .content(content)
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn send_message(&self, channel_id: u64, message: OutgoingMessage) -> anyhow::Result<()> {
+        ChannelId(channel_id)
+            .send_message(&self.0, |m| {
+                m.content(message.content);
+                if let Some(png) = message.image {
+                    m.add_file(AttachmentType::Bytes {
+                        data: Cow::Owned(png),
+                        filename: String::from("board.png"),
+                    });
+                }
+                if !message.buttons.is_empty() {
+                    m.components(|c| {
+                        for buttons in message.buttons {
+                            c.create_action_row(|row| {
+                                for button in buttons {
+                                    row.add_button(button.create_button(message.locale));
+                                }
+                                row
+                            });
+                        }
+                        c
+                    });
+                }
+                m
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn respond(&self, click: &ButtonClick, content: String) -> anyhow::Result<()> {
+        let mut response = CreateInteractionResponse::default();
+        response.interaction_response_data(|msg| msg.content(content));
+        let map = serenity::utils::hashmap_to_json_map(response.0);
+        self.0
+            .create_interaction_response(
+                click.interaction_id,
+                &click.token,
+                &serde_json::Value::Object(map),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn edit_buttons(
+        &self,
+        click: &ButtonClick,
+        buttons: Vec<Vec<FriendleButton>>,
+        locale: Locale,
+    ) -> anyhow::Result<()> {
+        ChannelId(click.channel_id)
+            .edit_message(&self.0, MessageId(click.message_id), |m| {
+                m.components(|c| {
+                    for buttons in buttons {
+                        c.create_action_row(|row| {
+                            for button in buttons {
+                                row.add_button(button.create_button(locale));
+                            }
+                            row
+                        });
+                    }
+                    c
+                })
+            })
+            .await?;
+        Ok(())
+    }
+}
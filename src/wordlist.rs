@@ -1,13 +1,123 @@
+use crate::model::difficulty::{Difficulty, DifficultyEstimator};
+use rand::seq::IteratorRandom;
 use serde::{Deserialize, Serialize};
 use serenity::prelude::TypeMapKey;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct WordList {
     pub words: HashSet<String>,
+    pub difficulty_estimator: DifficultyEstimator,
+    /// Estimated difficulty of every word in the list, computed once up front for `.random <difficulty>`.
+    difficulties: HashMap<String, Difficulty>,
 }
 
 impl TypeMapKey for WordList {
     type Value = Arc<WordList>;
 }
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct WordFilter {
+    /// Only allow words in which every letter occurs at most once.
+    pub unique_letters: bool,
+    /// Only allow words with the given estimated difficulty.
+    pub difficulty: Option<Difficulty>,
+}
+
+impl WordFilter {
+    pub fn matches(&self, word: &str, word_list: &WordList) -> bool {
+        (!self.unique_letters || itertools::Itertools::all_unique(&mut word.chars()))
+            && self
+                .difficulty
+                .is_none_or(|difficulty| word_list.difficulty(word) == difficulty)
+    }
+}
+
+impl WordList {
+    pub fn new(words: HashSet<String>) -> Self {
+        let difficulty_estimator = DifficultyEstimator::new(&words);
+        let difficulties = words
+            .iter()
+            .map(|word| (word.clone(), difficulty_estimator.estimate(word, &words)))
+            .collect();
+        Self {
+            words,
+            difficulty_estimator,
+            difficulties,
+        }
+    }
+
+    /// Estimated difficulty of the word; only words outside the word list are estimated on demand.
+    pub fn difficulty(&self, word: &str) -> Difficulty {
+        match self.difficulties.get(word) {
+            Some(difficulty) => *difficulty,
+            None => self.difficulty_estimator.estimate(word, &self.words),
+        }
+    }
+
+    /// Picks a uniformly random word from the word list that passes the given filter.
+    pub fn random_word(&self, filter: WordFilter) -> Option<&str> {
+        self.words
+            .iter()
+            .filter(|word| filter.matches(word, self))
+            .choose(&mut rand::thread_rng())
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_letters_filter() {
+        let word_list = WordList::default();
+        let filter = WordFilter {
+            unique_letters: true,
+            ..Default::default()
+        };
+        assert!(filter.matches("tales", &word_list));
+        assert!(!filter.matches("hello", &word_list));
+        assert!(WordFilter::default().matches("hello", &word_list));
+    }
+
+    #[test]
+    fn test_random_word_respects_filter() {
+        let word_list = WordList::new(HashSet::from_iter(
+            ["hello", "tales", "abbey"].map(String::from),
+        ));
+        let filter = WordFilter {
+            unique_letters: true,
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            assert_eq!(word_list.random_word(filter), Some("tales"));
+        }
+        assert!(WordList::default()
+            .random_word(WordFilter::default())
+            .is_none());
+    }
+
+    #[test]
+    fn test_cached_difficulty_matches_estimate() {
+        let word_list = WordList::new(HashSet::from_iter(
+            ["hello", "tales", "abbey", "jazzy"].map(String::from),
+        ));
+        for word in ["hello", "tales", "abbey", "jazzy", "fuzzy"] {
+            assert_eq!(
+                word_list.difficulty(word),
+                word_list
+                    .difficulty_estimator
+                    .estimate(word, &word_list.words)
+            );
+        }
+        let filter = WordFilter {
+            difficulty: Some(word_list.difficulty("jazzy")),
+            ..Default::default()
+        };
+        assert!(word_list
+            .random_word(filter)
+            .is_some_and(|word| word_list.difficulty(word) == word_list.difficulty("jazzy")));
+    }
+}